@@ -9,6 +9,19 @@ mod m20230902_025247_v1_create_permission_role;
 mod m20230902_025255_v1_create_role_user;
 mod m20230902_025309_v1_create_tokens;
 mod m20231216_092530_v1_user_initial_seeder;
+mod m20261016_080000_v1_create_password_histories;
+mod m20261016_090000_v1_alter_tokens_add_session_metadata;
+mod m20261016_100000_v1_alter_roles_add_parent_id;
+mod m20261016_110000_v1_create_audit_logs;
+mod m20261016_110100_v1_audit_permission_seeder;
+mod m20261016_120000_v1_create_api_keys;
+mod m20261016_130000_v1_cache_permission_seeder;
+mod m20261016_140000_v1_alter_users_add_last_login_at;
+mod m20261016_150000_v1_token_permission_seeder;
+mod m20261016_160000_v1_alter_tokens_add_impersonated_by;
+mod m20261016_160100_v1_impersonate_permission_seeder;
+mod m20261016_170000_v1_alter_tokens_add_session_id;
+mod permission_seeder;
 
 pub struct Migrator;
 
@@ -25,6 +38,18 @@ impl MigratorTrait for Migrator {
             Box::new(m20230902_025255_v1_create_role_user::Migration),
             Box::new(m20230902_025309_v1_create_tokens::Migration),
             Box::new(m20231216_092530_v1_user_initial_seeder::Migration),
+            Box::new(m20261016_080000_v1_create_password_histories::Migration),
+            Box::new(m20261016_090000_v1_alter_tokens_add_session_metadata::Migration),
+            Box::new(m20261016_100000_v1_alter_roles_add_parent_id::Migration),
+            Box::new(m20261016_110000_v1_create_audit_logs::Migration),
+            Box::new(m20261016_110100_v1_audit_permission_seeder::Migration),
+            Box::new(m20261016_120000_v1_create_api_keys::Migration),
+            Box::new(m20261016_130000_v1_cache_permission_seeder::Migration),
+            Box::new(m20261016_140000_v1_alter_users_add_last_login_at::Migration),
+            Box::new(m20261016_150000_v1_token_permission_seeder::Migration),
+            Box::new(m20261016_160000_v1_alter_tokens_add_impersonated_by::Migration),
+            Box::new(m20261016_160100_v1_impersonate_permission_seeder::Migration),
+            Box::new(m20261016_170000_v1_alter_tokens_add_session_id::Migration),
         ]
     }
 }
@@ -0,0 +1,103 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230902_024725_v1_create_users::{User, TABLE as USER_TABLE};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[cfg(feature = "postgres")]
+pub const TABLE: (PasswordHistory, PasswordHistory) =
+    (PasswordHistory::Schema, PasswordHistory::Table);
+#[cfg(not(feature = "postgres"))]
+pub const TABLE: PasswordHistory = PasswordHistory::Table;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        #[cfg(any(feature = "postgres", feature = "sqlite"))]
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PasswordHistory::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .extra(
+                                #[cfg(feature = "postgres")]
+                                "DEFAULT uuid_generate_v4()",
+                                #[cfg(feature = "sqlite")]
+                                "DEFAULT (hex(randomblob(16)))",
+                            ),
+                    )
+                    .col(ColumnDef::new(PasswordHistory::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(PasswordHistory::Password)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(PasswordHistory::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .extra(
+                                #[cfg(feature = "postgres")]
+                                "DEFAULT NOW()",
+                                #[cfg(feature = "sqlite")]
+                                "DEFAULT CURRENT_TIMESTAMP",
+                            ),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE, PasswordHistory::UserId)
+                            .to(USER_TABLE, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(TABLE)
+                    .col(PasswordHistory::UserId)
+                    .name("idx_password_histories_user_id")
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(TABLE)
+                    .col(PasswordHistory::CreatedAt)
+                    .name("idx_password_histories_created_at")
+                    .take(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().if_exists().table(TABLE).take())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum PasswordHistory {
+    #[cfg(feature = "postgres")]
+    #[sea_orm(iden = "v1")]
+    Schema,
+    #[sea_orm(iden = "password_histories")]
+    Table,
+    Id,
+    UserId,
+    Password,
+    CreatedAt,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[cfg(feature = "postgres")]
+const TABLE: (Token, Token) = (Token::Schema, Token::Table);
+#[cfg(not(feature = "postgres"))]
+const TABLE: Token = Token::Table;
+
+// sqlite only accepts a single column per `ALTER TABLE` statement
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .add_column(ColumnDef::new(Token::UserAgent).string().null())
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .add_column(ColumnDef::new(Token::IpAddress).string().null())
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .add_column(ColumnDef::new(Token::CreatedAt).timestamp().null())
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .add_column(ColumnDef::new(Token::LastUsedAt).timestamp().null())
+                    .take(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for column in [
+            Token::UserAgent,
+            Token::IpAddress,
+            Token::CreatedAt,
+            Token::LastUsedAt,
+        ] {
+            manager
+                .alter_table(Table::alter().table(TABLE).drop_column(column).take())
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Token {
+    #[cfg(feature = "postgres")]
+    #[sea_orm(iden = "v1")]
+    Schema,
+    #[sea_orm(iden = "tokens")]
+    Table,
+    UserAgent,
+    IpAddress,
+    CreatedAt,
+    LastUsedAt,
+}
@@ -0,0 +1,81 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230902_025106_v1_create_roles::TABLE;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .add_column(ColumnDef::new(Role::ParentId).uuid().null())
+                    .take(),
+            )
+            .await?;
+
+        // sqlite can not add a foreign key to an existing table
+        #[cfg(feature = "postgres")]
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_roles_parent_id")
+                    .from(TABLE, Role::ParentId)
+                    .to(TABLE, Role::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(TABLE)
+                    .col(Role::ParentId)
+                    .name("idx_roles_parent_id")
+                    .take(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .table(TABLE)
+                    .name("idx_roles_parent_id")
+                    .take(),
+            )
+            .await?;
+
+        #[cfg(feature = "postgres")]
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .table(TABLE)
+                    .name("fk_roles_parent_id")
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .drop_column(Role::ParentId)
+                    .take(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Role {
+    Id,
+    ParentId,
+}
@@ -0,0 +1,108 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[cfg(feature = "postgres")]
+pub const TABLE: (AuditLog, AuditLog) = (AuditLog::Schema, AuditLog::Table);
+#[cfg(not(feature = "postgres"))]
+pub const TABLE: AuditLog = AuditLog::Table;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        #[cfg(any(feature = "postgres", feature = "sqlite"))]
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditLog::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .extra(
+                                #[cfg(feature = "postgres")]
+                                "DEFAULT uuid_generate_v4()",
+                                #[cfg(feature = "sqlite")]
+                                "DEFAULT (hex(randomblob(16)))",
+                            ),
+                    )
+                    .col(ColumnDef::new(AuditLog::ActorUserId).uuid().null())
+                    .col(ColumnDef::new(AuditLog::Action).string().not_null())
+                    .col(ColumnDef::new(AuditLog::TargetType).string().null())
+                    .col(ColumnDef::new(AuditLog::TargetId).uuid().null())
+                    .col(ColumnDef::new(AuditLog::Ip).string().null())
+                    .col(ColumnDef::new(AuditLog::Metadata).json().null())
+                    .col(
+                        ColumnDef::new(AuditLog::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .extra(
+                                #[cfg(feature = "postgres")]
+                                "DEFAULT NOW()",
+                                #[cfg(feature = "sqlite")]
+                                "DEFAULT CURRENT_TIMESTAMP",
+                            ),
+                    )
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(TABLE)
+                    .col(AuditLog::ActorUserId)
+                    .name("idx_audit_logs_actor_user_id")
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(TABLE)
+                    .col(AuditLog::Action)
+                    .name("idx_audit_logs_action")
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(TABLE)
+                    .col(AuditLog::CreatedAt)
+                    .name("idx_audit_logs_created_at")
+                    .take(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().if_exists().table(TABLE).take())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum AuditLog {
+    #[cfg(feature = "postgres")]
+    #[sea_orm(iden = "v1")]
+    Schema,
+    #[sea_orm(iden = "audit_logs")]
+    Table,
+    Id,
+    ActorUserId,
+    Action,
+    TargetType,
+    TargetId,
+    Ip,
+    Metadata,
+    CreatedAt,
+}
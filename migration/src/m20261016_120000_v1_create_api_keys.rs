@@ -0,0 +1,106 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230902_024725_v1_create_users::{User, TABLE as USER_TABLE};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[cfg(feature = "postgres")]
+pub const TABLE: (ApiKey, ApiKey) = (ApiKey::Schema, ApiKey::Table);
+#[cfg(not(feature = "postgres"))]
+pub const TABLE: ApiKey = ApiKey::Table;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        #[cfg(any(feature = "postgres", feature = "sqlite"))]
+        manager
+            .create_table(
+                Table::create()
+                    .table(TABLE)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ApiKey::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key()
+                            .extra(
+                                #[cfg(feature = "postgres")]
+                                "DEFAULT uuid_generate_v4()",
+                                #[cfg(feature = "sqlite")]
+                                "DEFAULT (hex(randomblob(16)))",
+                            ),
+                    )
+                    .col(ColumnDef::new(ApiKey::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(ApiKey::Prefix)
+                            .string()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(ApiKey::HashedSecret).string().not_null())
+                    .col(ColumnDef::new(ApiKey::Scopes).string().not_null())
+                    .col(
+                        ColumnDef::new(ApiKey::ExpiredAt)
+                            .timestamp()
+                            .null()
+                            .extra("default null"),
+                    )
+                    .col(ColumnDef::new(ApiKey::LastUsedAt).timestamp().null())
+                    .col(
+                        ColumnDef::new(ApiKey::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .extra(
+                                #[cfg(feature = "postgres")]
+                                "DEFAULT NOW()",
+                                #[cfg(feature = "sqlite")]
+                                "DEFAULT CURRENT_TIMESTAMP",
+                            ),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .from(TABLE, ApiKey::UserId)
+                            .to(USER_TABLE, User::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(TABLE)
+                    .col(ApiKey::UserId)
+                    .name("idx_api_keys_user_id")
+                    .take(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().if_exists().table(TABLE).take())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+pub enum ApiKey {
+    #[cfg(feature = "postgres")]
+    #[sea_orm(iden = "v1")]
+    Schema,
+    #[sea_orm(iden = "api_keys")]
+    Table,
+    Id,
+    UserId,
+    Prefix,
+    HashedSecret,
+    Scopes,
+    ExpiredAt,
+    LastUsedAt,
+    CreatedAt,
+}
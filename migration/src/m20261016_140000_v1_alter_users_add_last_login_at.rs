@@ -0,0 +1,36 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230902_024725_v1_create_users::TABLE;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .add_column(ColumnDef::new(User::LastLoginAt).timestamp().null())
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .drop_column(User::LastLoginAt)
+                    .take(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum User {
+    LastLoginAt,
+}
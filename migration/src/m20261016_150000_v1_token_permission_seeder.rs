@@ -0,0 +1,20 @@
+use sea_orm_migration::prelude::*;
+
+use crate::permission_seeder::{self, ROLES};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+const CODE: &str = "REVOKE_TOKEN";
+const NAME: &str = "revoke token";
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        permission_seeder::seed(manager, CODE, NAME, &ROLES).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        permission_seeder::unseed(manager, CODE).await
+    }
+}
@@ -0,0 +1,44 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[cfg(feature = "postgres")]
+const TABLE: (Token, Token) = (Token::Schema, Token::Table);
+#[cfg(not(feature = "postgres"))]
+const TABLE: Token = Token::Table;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .add_column(ColumnDef::new(Token::ImpersonatedBy).uuid().null())
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .drop_column(Token::ImpersonatedBy)
+                    .take(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Token {
+    #[cfg(feature = "postgres")]
+    #[sea_orm(iden = "v1")]
+    Schema,
+    #[sea_orm(iden = "tokens")]
+    Table,
+    ImpersonatedBy,
+}
@@ -0,0 +1,214 @@
+use lighter_common::prelude::*;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[cfg(feature = "postgres")]
+const TABLE: (Token, Token) = (Token::Schema, Token::Table);
+#[cfg(not(feature = "postgres"))]
+const TABLE: Token = Token::Table;
+
+// the token id is the bearer credential, sessions are listed and revoked by this handle instead
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let db = manager.get_connection();
+        let backend = manager.get_database_backend();
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .add_column(ColumnDef::new(Token::SessionId).uuid().null())
+                    .take(),
+            )
+            .await?;
+
+        let tokens = db
+            .query_all(backend.build(&Query::select().column(Token::Id).from(TABLE).to_owned()))
+            .await?;
+
+        for token in tokens {
+            let id: Uuid = token.try_get("", "id")?;
+
+            manager
+                .exec_stmt(
+                    Query::update()
+                        .table(TABLE)
+                        .value(Token::SessionId, Uuid::new_v4())
+                        .and_where(Expr::col(Token::Id).eq(id))
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        // every row has a handle now, so the column can match the entity
+        #[cfg(feature = "postgres")]
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .modify_column(ColumnDef::new(Token::SessionId).uuid().not_null())
+                    .take(),
+            )
+            .await?;
+
+        #[cfg(feature = "sqlite")]
+        rebuild(manager).await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .table(TABLE)
+                    .col(Token::SessionId)
+                    .name("idx_tokens_session_id")
+                    .unique()
+                    .take(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .table(TABLE)
+                    .name("idx_tokens_session_id")
+                    .take(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TABLE)
+                    .drop_column(Token::SessionId)
+                    .take(),
+            )
+            .await
+    }
+}
+
+/// sqlite can't alter a column, so the table is copied into one declaring
+/// session_id as not null and swapped in, its indexes are dropped along the way
+#[cfg(feature = "sqlite")]
+async fn rebuild(manager: &SchemaManager<'_>) -> Result<(), DbErr> {
+    let columns = || {
+        [
+            Token::Id,
+            Token::UserId,
+            Token::ExpiredAt,
+            Token::UserAgent,
+            Token::IpAddress,
+            Token::CreatedAt,
+            Token::LastUsedAt,
+            Token::ImpersonatedBy,
+            Token::SessionId,
+        ]
+    };
+
+    manager
+        .create_table(
+            Table::create()
+                .table(Token::Rebuild)
+                .col(
+                    ColumnDef::new(Token::Id)
+                        .uuid()
+                        .not_null()
+                        .primary_key()
+                        .extra("DEFAULT (hex(randomblob(16)))"),
+                )
+                .col(ColumnDef::new(Token::UserId).uuid().not_null())
+                .col(
+                    ColumnDef::new(Token::ExpiredAt)
+                        .timestamp()
+                        .null()
+                        .extra("default null"),
+                )
+                .col(ColumnDef::new(Token::UserAgent).string().null())
+                .col(ColumnDef::new(Token::IpAddress).string().null())
+                .col(ColumnDef::new(Token::CreatedAt).timestamp().null())
+                .col(ColumnDef::new(Token::LastUsedAt).timestamp().null())
+                .col(ColumnDef::new(Token::ImpersonatedBy).uuid().null())
+                .col(ColumnDef::new(Token::SessionId).uuid().not_null())
+                .foreign_key(
+                    ForeignKey::create()
+                        .from(Token::Rebuild, Token::UserId)
+                        .to(Token::Users, Token::Id)
+                        .on_delete(ForeignKeyAction::Cascade),
+                )
+                .take(),
+        )
+        .await?;
+
+    manager
+        .exec_stmt(
+            Query::insert()
+                .into_table(Token::Rebuild)
+                .columns(columns())
+                .select_from(Query::select().columns(columns()).from(TABLE).to_owned())
+                .map_err(|e| DbErr::Migration(e.to_string()))?
+                .to_owned(),
+        )
+        .await?;
+
+    manager
+        .drop_table(Table::drop().table(TABLE).take())
+        .await?;
+
+    manager
+        .rename_table(Table::rename().table(Token::Rebuild, TABLE).take())
+        .await?;
+
+    manager
+        .create_index(
+            Index::create()
+                .table(TABLE)
+                .col(Token::UserId)
+                .name("idx_token_user_id")
+                .take(),
+        )
+        .await?;
+
+    manager
+        .create_index(
+            Index::create()
+                .table(TABLE)
+                .col(Token::ExpiredAt)
+                .name("idx_token_expired_at")
+                .take(),
+        )
+        .await
+}
+
+#[derive(DeriveIden)]
+enum Token {
+    #[cfg(feature = "postgres")]
+    #[sea_orm(iden = "v1")]
+    Schema,
+    #[sea_orm(iden = "tokens")]
+    Table,
+    #[cfg(feature = "sqlite")]
+    #[sea_orm(iden = "tokens_rebuild")]
+    Rebuild,
+    #[cfg(feature = "sqlite")]
+    #[sea_orm(iden = "users")]
+    Users,
+    Id,
+    #[cfg(feature = "sqlite")]
+    UserId,
+    #[cfg(feature = "sqlite")]
+    ExpiredAt,
+    #[cfg(feature = "sqlite")]
+    UserAgent,
+    #[cfg(feature = "sqlite")]
+    IpAddress,
+    #[cfg(feature = "sqlite")]
+    CreatedAt,
+    #[cfg(feature = "sqlite")]
+    LastUsedAt,
+    #[cfg(feature = "sqlite")]
+    ImpersonatedBy,
+    SessionId,
+}
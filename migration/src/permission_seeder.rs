@@ -0,0 +1,86 @@
+use lighter_common::prelude::*;
+use sea_orm_migration::prelude::*;
+
+use crate::{
+    m20230902_024928_v1_create_permissions::{Permission, TABLE as PERMISSION_TABLE},
+    m20230902_025106_v1_create_roles::{Role, TABLE as ROLE_TABLE},
+    m20230902_025247_v1_create_permission_role::{PermissionRole, TABLE as PERMISSION_ROLE_TABLE},
+};
+
+/// Roles granted the permissions added after the initial seeder
+pub const ROLES: [&str; 2] = ["SUPERUSER", "ADMIN"];
+
+/// Insert a permission and grant it to the existing `roles`
+pub async fn seed(
+    manager: &SchemaManager<'_>,
+    code: &str,
+    name: &str,
+    roles: &[&str],
+) -> Result<(), DbErr> {
+    let db = manager.get_connection();
+    let backend = manager.get_database_backend();
+    let permission_id = Uuid::new_v4();
+
+    manager
+        .exec_stmt(
+            Query::insert()
+                .into_table(PERMISSION_TABLE)
+                .columns(vec![Permission::Id, Permission::Code, Permission::Name])
+                .values_panic(vec![permission_id.into(), code.into(), name.into()])
+                .to_owned(),
+        )
+        .await?;
+
+    let roles = db
+        .query_all(
+            backend.build(
+                &Query::select()
+                    .column(Role::Id)
+                    .from(ROLE_TABLE)
+                    .and_where(Expr::col(Role::Code).is_in(roles.iter().copied()))
+                    .to_owned(),
+            ),
+        )
+        .await?;
+
+    if roles.is_empty() {
+        return Ok(());
+    }
+
+    let mut permission_role = Query::insert()
+        .into_table(PERMISSION_ROLE_TABLE)
+        .columns(vec![
+            PermissionRole::Id,
+            PermissionRole::PermissionId,
+            PermissionRole::RoleId,
+        ])
+        .to_owned();
+
+    for role in roles {
+        let role: Uuid = role.try_get("", "id")?;
+
+        permission_role = permission_role
+            .values_panic(vec![
+                Uuid::new_v4().into(),
+                permission_id.into(),
+                role.into(),
+            ])
+            .to_owned();
+    }
+
+    manager.exec_stmt(permission_role).await?;
+
+    Ok(())
+}
+
+/// Delete a permission seeded by [`seed`]
+pub async fn unseed(manager: &SchemaManager<'_>, code: &str) -> Result<(), DbErr> {
+    manager
+        .exec_stmt(
+            Query::delete()
+                .from_table(PERMISSION_TABLE)
+                .and_where(Expr::col(Permission::Code).eq(code))
+                .to_owned(),
+        )
+        .await
+}
@@ -1,5 +1,6 @@
 use lighter_common::api::{Authentication, Builtin};
-use utoipa::OpenApi;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, SecurityScheme};
+use utoipa::{Modify, OpenApi};
 
 use crate::{controllers, requests, responses};
 
@@ -10,15 +11,26 @@ use crate::{controllers, requests, responses};
         (name = "User"),
         (name = "Permission"),
         (name = "Role"),
+        (name = "Audit"),
+        (name = "Cache"),
+        (name = "Impersonation"),
+        (name = "Health"),
     ),
-    modifiers(&Builtin, &Authentication),
+    modifiers(&Builtin, &Authentication, &ApiKeyAuthentication),
     paths(
         controllers::v1::user::paginate,
         controllers::v1::user::store,
+        controllers::v1::user::bulk,
         controllers::v1::user::show,
+        controllers::v1::user::export,
+        controllers::v1::user::session_count,
         controllers::v1::user::update_general_information,
         controllers::v1::user::update_password,
+        controllers::v1::user::change_email,
         controllers::v1::user::delete,
+        controllers::v1::user::restore,
+        controllers::v1::user::assign_permissions,
+        controllers::v1::user::revoke_permissions,
 
         controllers::v1::permission::paginate,
         controllers::v1::permission::store,
@@ -34,22 +46,67 @@ use crate::{controllers, requests, responses};
 
         controllers::v1::auth::login,
         controllers::v1::auth::authenticated,
+        controllers::v1::auth::me,
+        controllers::v1::auth::can,
         controllers::v1::auth::logout,
+        controllers::v1::auth::logout_others,
+        controllers::v1::auth::sessions,
+        controllers::v1::auth::revoke_session,
+        controllers::v1::auth::revoke_token,
+        controllers::v1::auth::create_api_key,
+        controllers::v1::auth::revoke_api_key,
+        controllers::v1::auth::send_verification,
+        controllers::v1::auth::verify_email,
+        controllers::v1::auth::password_strength,
+
+        controllers::v1::audit::paginate,
+
+        controllers::v1::cache::stats,
+        controllers::v1::cache::clear,
+
+        controllers::v1::impersonation::impersonate,
+
+        controllers::v1::health::live,
+        controllers::v1::health::ready,
     ),
     components(schemas(
+        requests::v1::auth::ApiKeyRequest,
         requests::v1::auth::LoginRequest,
+        requests::v1::auth::PasswordStrengthRequest,
+        requests::v1::auth::PermissionCheckRequest,
+        requests::v1::auth::VerifyEmailRequest,
+        requests::v1::user::UserDeleteFilter,
+        requests::v1::user::UserEmailRequest,
+        requests::v1::user::UserPaginationFilter,
+        requests::v1::user::UserPermissionsRequest,
         requests::v1::user::UserStoreRequest,
         requests::v1::user::UserUpdateGeneralInformationRequest,
         requests::v1::user::UserUpdatePasswordRequest,
         requests::v1::permission::PermissionRequest,
+        requests::v1::role::RoleInclude,
         requests::v1::role::RoleRequest,
 
+        responses::v1::auth::ApiKey,
+        responses::v1::auth::Me,
+        responses::v1::auth::PasswordStrength,
+        responses::v1::auth::Session,
+        responses::v1::auth::SessionCount,
+        responses::v1::auth::SlimAuthenticated,
+        responses::v1::auth::VerificationSent,
+
+        responses::v1::conflict::Conflict,
+
+        responses::v1::forbidden::Forbidden,
+
         responses::v1::user::simple::User,
         responses::v1::user::simple::UserPaginationSort,
         responses::v1::user::simple::UserPaginationOrder,
         responses::v1::user::simple::UserPaginationRequest,
         responses::v1::user::simple::UserPaginationResponse,
         responses::v1::user::complete::UserWithPermissionAndRole,
+        responses::v1::user::bulk::BulkUserResult,
+        responses::v1::user::bulk::BulkUserResponse,
+        responses::v1::user::export::UserExport,
 
         responses::v1::permission::Permission,
         responses::v1::permission::PermissionPaginationSort,
@@ -62,6 +119,33 @@ use crate::{controllers, requests, responses};
         responses::v1::role::RolePaginationOrder,
         responses::v1::role::RolePaginationRequest,
         responses::v1::role::RolePaginationResponse,
+        responses::v1::role::RoleWithPermissions,
+        responses::v1::role::RoleWithPermissionsPaginationResponse,
+
+        responses::v1::audit::AuditLog,
+        responses::v1::audit::AuditLogPaginationSort,
+        responses::v1::audit::AuditLogPaginationOrder,
+        responses::v1::audit::AuditLogPaginationRequest,
+        responses::v1::audit::AuditLogPaginationResponse,
+
+        responses::v1::cache::CacheCleared,
+        responses::v1::cache::CacheStats,
+
+        responses::v1::health::Health,
     )),
 )]
 pub struct Definition;
+
+/// Declares the `X-Api-Key` header accepted in place of a bearer token
+pub struct ApiKeyAuthentication;
+
+impl Modify for ApiKeyAuthentication {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Api-Key"))),
+            );
+        }
+    }
+}
@@ -0,0 +1,493 @@
+use std::env;
+use std::fs;
+use std::str::FromStr;
+use std::time::Duration;
+
+use lighter_common::prelude::*;
+
+use crate::services::v1::auth::password_strength;
+
+/// Authentication behaviour, read from environment variables on startup.
+///
+/// Any variable can instead be given as `<NAME>_FILE`, the path of a file holding the value
+#[derive(Clone, Debug)]
+pub struct AuthConfig {
+    /// `AUTH_REQUIRE_VERIFIED_EMAIL`, reject login until the email is verified
+    pub require_verified_email: bool,
+    /// `AUTH_EMAIL_VERIFICATION_LIFETIME`, in seconds
+    pub email_verification_lifetime: Duration,
+    /// `AUTH_PASSWORD_HISTORY_SIZE`, previous passwords that can't be reused, 0 disables
+    pub password_history_size: usize,
+    /// Rules new passwords must satisfy
+    pub password_policy: PasswordPolicy,
+    /// `AUTH_CANONICALIZE_GMAIL`, drop dots and `+tag` from gmail addresses
+    /// so `j.doe+x@gmail.com` and `jdoe@gmail.com` are the same account
+    pub canonicalize_gmail: bool,
+    /// `AUTH_LOGIN_IDENTIFIER`, `email`, `username` or `both`
+    pub login_identifier: LoginIdentifier,
+    /// `AUTH_ACCESS_TOKEN_TTL`, in seconds, applied when no expiry is requested
+    pub access_token_ttl: Duration,
+    /// `AUTH_MAX_TOKEN_TTL`, in seconds, longer requested expiries are clamped
+    pub max_token_ttl: Duration,
+    /// `AUTH_ALLOW_NON_EXPIRING_TOKENS`, issue tokens without expiry when none is requested
+    pub allow_non_expiring_tokens: bool,
+    /// `AUTH_MAX_ASSIGNMENTS`, permissions or roles a single user request may assign
+    pub max_assignments: usize,
+    /// `AUTH_DORMANT_AFTER`, in seconds, tokens of users who haven't logged in
+    /// for longer are rejected until they log in again, 0 disables
+    pub dormant_after: Option<Duration>,
+    /// `AUTH_MAX_BULK_USERS`, users a single bulk import may create
+    pub max_bulk_users: usize,
+    /// `AUTH_DEFAULT_ROLES`, comma separated role codes given to new users
+    /// whose request doesn't assign any role
+    pub default_roles: Vec<String>,
+    /// `AUTH_DEFAULT_PERMISSIONS`, comma separated permission codes given to new
+    /// users whose request doesn't assign any permission
+    pub default_permissions: Vec<String>,
+    /// `AUTH_SESSION_MODE`, `absolute` or `sliding`
+    pub session_mode: SessionMode,
+    /// `AUTH_SESSION_IDLE_TIMEOUT`, in seconds, how long a sliding session survives without use
+    pub session_idle_timeout: Duration,
+    /// `AUTH_LOGIN_EMBED_RBAC`, include permissions and roles of the user in the
+    /// login response, without them clients fetch those from `/v1/auth/me`
+    pub login_embed_rbac: bool,
+    /// `AUTH_IMPERSONATION_TTL`, in seconds, lifetime of impersonation tokens
+    pub impersonation_ttl: Duration,
+    /// `AUTH_IMPERSONATION_PROTECTED`, comma separated permission codes whose holders
+    /// can't be impersonated
+    pub impersonation_protected: Vec<String>,
+}
+
+impl AuthConfig {
+    pub fn env() -> Self {
+        let default = Self::default();
+
+        Self {
+            require_verified_email: flag(
+                "AUTH_REQUIRE_VERIFIED_EMAIL",
+                default.require_verified_email,
+            ),
+            email_verification_lifetime: seconds(
+                "AUTH_EMAIL_VERIFICATION_LIFETIME",
+                default.email_verification_lifetime,
+            ),
+            password_history_size: number(
+                "AUTH_PASSWORD_HISTORY_SIZE",
+                default.password_history_size,
+            ),
+            password_policy: PasswordPolicy::env(),
+            canonicalize_gmail: flag("AUTH_CANONICALIZE_GMAIL", default.canonicalize_gmail),
+            login_identifier: LoginIdentifier::env(
+                "AUTH_LOGIN_IDENTIFIER",
+                default.login_identifier,
+            ),
+            access_token_ttl: seconds("AUTH_ACCESS_TOKEN_TTL", default.access_token_ttl),
+            max_token_ttl: seconds("AUTH_MAX_TOKEN_TTL", default.max_token_ttl),
+            allow_non_expiring_tokens: flag(
+                "AUTH_ALLOW_NON_EXPIRING_TOKENS",
+                default.allow_non_expiring_tokens,
+            ),
+            max_assignments: number("AUTH_MAX_ASSIGNMENTS", default.max_assignments),
+            dormant_after: optional_seconds("AUTH_DORMANT_AFTER", default.dormant_after),
+            max_bulk_users: number("AUTH_MAX_BULK_USERS", default.max_bulk_users),
+            default_roles: list("AUTH_DEFAULT_ROLES", default.default_roles),
+            default_permissions: list("AUTH_DEFAULT_PERMISSIONS", default.default_permissions),
+            session_mode: SessionMode::env("AUTH_SESSION_MODE", default.session_mode),
+            session_idle_timeout: seconds(
+                "AUTH_SESSION_IDLE_TIMEOUT",
+                default.session_idle_timeout,
+            ),
+            login_embed_rbac: flag("AUTH_LOGIN_EMBED_RBAC", default.login_embed_rbac),
+            impersonation_ttl: seconds("AUTH_IMPERSONATION_TTL", default.impersonation_ttl),
+            impersonation_protected: list(
+                "AUTH_IMPERSONATION_PROTECTED",
+                default.impersonation_protected,
+            ),
+        }
+    }
+
+    /// Check settings that only make sense together, returning every problem found
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = vec![];
+        let policy = &self.password_policy;
+
+        if policy.min_length > policy.max_length {
+            errors.push(format!(
+                "AUTH_PASSWORD_MIN_LENGTH ({}) must not exceed AUTH_PASSWORD_MAX_LENGTH ({})",
+                policy.min_length, policy.max_length
+            ));
+        }
+
+        if policy.min_strength > 4 {
+            errors.push(format!(
+                "AUTH_PASSWORD_MIN_STRENGTH ({}) must be between 0 and 4",
+                policy.min_strength
+            ));
+        }
+
+        if self.max_token_ttl.is_zero() {
+            errors.push("AUTH_MAX_TOKEN_TTL must be greater than 0".to_string());
+        }
+
+        if self.access_token_ttl.is_zero() {
+            errors.push("AUTH_ACCESS_TOKEN_TTL must be greater than 0".to_string());
+        }
+
+        if self.access_token_ttl > self.max_token_ttl {
+            errors.push(format!(
+                "AUTH_ACCESS_TOKEN_TTL ({}s) must not exceed AUTH_MAX_TOKEN_TTL ({}s)",
+                self.access_token_ttl.as_secs(),
+                self.max_token_ttl.as_secs()
+            ));
+        }
+
+        if self.impersonation_ttl.is_zero() {
+            errors.push("AUTH_IMPERSONATION_TTL must be greater than 0".to_string());
+        }
+
+        if self.email_verification_lifetime.is_zero() {
+            errors.push("AUTH_EMAIL_VERIFICATION_LIFETIME must be greater than 0".to_string());
+        }
+
+        if self.session_mode == SessionMode::Sliding {
+            if self.session_idle_timeout.is_zero() {
+                errors.push(
+                    "AUTH_SESSION_IDLE_TIMEOUT must be greater than 0 in sliding mode".to_string(),
+                );
+            }
+
+            if self.session_idle_timeout > self.max_token_ttl {
+                errors.push(format!(
+                    "AUTH_SESSION_IDLE_TIMEOUT ({}s) must not exceed AUTH_MAX_TOKEN_TTL ({}s)",
+                    self.session_idle_timeout.as_secs(),
+                    self.max_token_ttl.as_secs()
+                ));
+            }
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+
+    /// Expiry of a new token, `requested` is clamped to `max_token_ttl` and
+    /// defaults to `access_token_ttl` unless non-expiring tokens are allowed,
+    /// sliding sessions start with `session_idle_timeout` instead
+    pub fn token_expiry(&self, requested: Option<NaiveDateTime>) -> Option<NaiveDateTime> {
+        let issued_at = now();
+        let max = issued_at + self.max_token_ttl;
+
+        match requested {
+            Some(expired_at) => Some(expired_at.min(max)),
+            None if self.session_mode == SessionMode::Sliding => {
+                Some(issued_at + self.session_idle_timeout.min(self.max_token_ttl))
+            }
+            None if self.allow_non_expiring_tokens => None,
+            None => Some(issued_at + self.access_token_ttl.min(self.max_token_ttl)),
+        }
+    }
+
+    /// Whether the last login is older than `dormant_after`, users who never
+    /// logged in since the column was added have nothing to judge and pass
+    pub fn is_dormant(&self, last_login_at: Option<NaiveDateTime>) -> bool {
+        match (self.dormant_after, last_login_at) {
+            (Some(threshold), Some(last_login_at)) => last_login_at + threshold < now(),
+            _ => false,
+        }
+    }
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            require_verified_email: false,
+            // 1 day
+            email_verification_lifetime: Duration::from_secs(60 * 60 * 24),
+            password_history_size: 5,
+            password_policy: PasswordPolicy::default(),
+            canonicalize_gmail: false,
+            login_identifier: LoginIdentifier::Both,
+            // 1 day
+            access_token_ttl: Duration::from_secs(60 * 60 * 24),
+            // 30 days
+            max_token_ttl: Duration::from_secs(60 * 60 * 24 * 30),
+            allow_non_expiring_tokens: false,
+            max_assignments: 100,
+            dormant_after: None,
+            max_bulk_users: 100,
+            default_roles: vec![],
+            default_permissions: vec![],
+            session_mode: SessionMode::Absolute,
+            // 30 minutes
+            session_idle_timeout: Duration::from_secs(60 * 30),
+            login_embed_rbac: true,
+            // 15 minutes
+            impersonation_ttl: Duration::from_secs(60 * 15),
+            impersonation_protected: vec!["IMPERSONATE".to_string()],
+        }
+    }
+}
+
+/// Which identifier login accepts, restricting it avoids username enumeration
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoginIdentifier {
+    Email,
+    Username,
+    Both,
+}
+
+impl LoginIdentifier {
+    fn env(key: &str, default: Self) -> Self {
+        match var(key) {
+            Ok(value) => match value.trim().to_lowercase().as_str() {
+                "email" => Self::Email,
+                "username" => Self::Username,
+                "both" => Self::Both,
+                _ => {
+                    tracing::error!("Invalid value for {}, using default", key);
+
+                    default
+                }
+            },
+            Err(_) => default,
+        }
+    }
+
+    pub fn allows_email(&self) -> bool {
+        matches!(self, Self::Email | Self::Both)
+    }
+
+    pub fn allows_username(&self) -> bool {
+        matches!(self, Self::Username | Self::Both)
+    }
+}
+
+/// How token expiry evolves after login
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SessionMode {
+    /// Tokens expire at the time fixed on login
+    Absolute,
+    /// Every authenticated request pushes the expiry `session_idle_timeout` ahead
+    Sliding,
+}
+
+impl SessionMode {
+    fn env(key: &str, default: Self) -> Self {
+        match var(key) {
+            Ok(value) => match value.trim().to_lowercase().as_str() {
+                "absolute" => Self::Absolute,
+                "sliding" => Self::Sliding,
+                _ => {
+                    tracing::error!("Invalid value for {}, using default", key);
+
+                    default
+                }
+            },
+            Err(_) => default,
+        }
+    }
+}
+
+/// Password rules, read from `AUTH_PASSWORD_*` environment variables.
+#[derive(Clone, Debug)]
+pub struct PasswordPolicy {
+    /// `AUTH_PASSWORD_MIN_LENGTH`, in characters
+    pub min_length: usize,
+    /// `AUTH_PASSWORD_MAX_LENGTH`, in characters
+    pub max_length: usize,
+    /// `AUTH_PASSWORD_REQUIRE_UPPERCASE`
+    pub require_uppercase: bool,
+    /// `AUTH_PASSWORD_REQUIRE_LOWERCASE`
+    pub require_lowercase: bool,
+    /// `AUTH_PASSWORD_REQUIRE_DIGIT`
+    pub require_digit: bool,
+    /// `AUTH_PASSWORD_REQUIRE_SYMBOL`
+    pub require_symbol: bool,
+    /// `AUTH_PASSWORD_DISALLOW_USERNAME`, reject passwords containing the username
+    pub disallow_username: bool,
+    /// `AUTH_PASSWORD_DISALLOWED`, comma separated substrings that are rejected
+    pub disallowed: Vec<String>,
+    /// `AUTH_PASSWORD_MIN_STRENGTH`, lowest accepted strength score from 0 to 4, 0 disables
+    pub min_strength: u8,
+}
+
+impl PasswordPolicy {
+    pub fn env() -> Self {
+        let default = Self::default();
+
+        Self {
+            min_length: number("AUTH_PASSWORD_MIN_LENGTH", default.min_length),
+            max_length: number("AUTH_PASSWORD_MAX_LENGTH", default.max_length),
+            require_uppercase: flag("AUTH_PASSWORD_REQUIRE_UPPERCASE", default.require_uppercase),
+            require_lowercase: flag("AUTH_PASSWORD_REQUIRE_LOWERCASE", default.require_lowercase),
+            require_digit: flag("AUTH_PASSWORD_REQUIRE_DIGIT", default.require_digit),
+            require_symbol: flag("AUTH_PASSWORD_REQUIRE_SYMBOL", default.require_symbol),
+            disallow_username: flag("AUTH_PASSWORD_DISALLOW_USERNAME", default.disallow_username),
+            disallowed: list("AUTH_PASSWORD_DISALLOWED", default.disallowed),
+            min_strength: number("AUTH_PASSWORD_MIN_STRENGTH", default.min_strength),
+        }
+    }
+
+    /// Check the password against every rule, returning a message per failed rule
+    pub fn validate(&self, password: &str, username: &str) -> Vec<String> {
+        let mut errors = vec![];
+        let length = password.chars().count();
+        let lowercase = password.to_lowercase();
+
+        if length < self.min_length {
+            errors.push(format!(
+                "Password must be at least {} characters.",
+                self.min_length
+            ));
+        }
+
+        if length > self.max_length {
+            errors.push(format!(
+                "Password must be at most {} characters.",
+                self.max_length
+            ));
+        }
+
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            errors.push("Password must contain an uppercase letter.".to_string());
+        }
+
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            errors.push("Password must contain a lowercase letter.".to_string());
+        }
+
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            errors.push("Password must contain a digit.".to_string());
+        }
+
+        if self.require_symbol && password.chars().all(char::is_alphanumeric) {
+            errors.push("Password must contain a symbol.".to_string());
+        }
+
+        let username = username.trim().to_lowercase();
+
+        if self.disallow_username && !username.is_empty() && lowercase.contains(&username) {
+            errors.push("Password must not contain the username.".to_string());
+        }
+
+        for disallowed in &self.disallowed {
+            if lowercase.contains(&disallowed.to_lowercase()) {
+                errors.push(format!("Password must not contain \"{}\".", disallowed));
+            }
+        }
+
+        if self.min_strength > 0 {
+            let strength = password_strength::strength(password, &username);
+
+            if strength.score < self.min_strength {
+                errors.push("Password is too weak.".to_string());
+                errors.extend(strength.suggestions);
+            }
+        }
+
+        errors
+    }
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: 128,
+            require_uppercase: false,
+            require_lowercase: false,
+            require_digit: false,
+            require_symbol: false,
+            disallow_username: false,
+            disallowed: vec![],
+            min_strength: 0,
+        }
+    }
+}
+
+/// Read `key`, preferring the contents of the file named by `<key>_FILE` so
+/// secrets can be mounted as files, trailing newlines are trimmed
+pub fn var(key: &str) -> Result<String, env::VarError> {
+    let file = format!("{}_FILE", key);
+    let path = match env::var(&file) {
+        Ok(path) => path,
+        Err(_) => return env::var(key),
+    };
+
+    match fs::read_to_string(path.trim()) {
+        Ok(value) => {
+            if env::var(key).is_ok() {
+                tracing::warn!("Both {} and {} are set, using {}", key, file, file);
+            }
+
+            Ok(value.trim_end_matches(['\r', '\n']).to_string())
+        }
+        Err(e) => {
+            tracing::error!("Failed to read {}", file);
+            tracing::error!("Error: {}", e);
+
+            env::var(key)
+        }
+    }
+}
+
+/// Resolve `<key>_FILE` into `key` for settings read outside this crate,
+/// like `DATABASE_URL` which lighter_common reads on its own
+pub fn load_secret_files(keys: &[&str]) {
+    for key in keys {
+        if env::var(format!("{}_FILE", key)).is_err() {
+            continue;
+        }
+
+        if let Ok(value) = var(key) {
+            env::set_var(key, value);
+        }
+    }
+}
+
+fn flag(key: &str, default: bool) -> bool {
+    match var(key) {
+        Ok(value) => matches!(
+            value.trim().to_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        ),
+        Err(_) => default,
+    }
+}
+
+fn number<T: FromStr>(key: &str, default: T) -> T {
+    match var(key) {
+        Ok(value) => match value.trim().parse() {
+            Ok(value) => value,
+            Err(_) => {
+                tracing::error!("Invalid value for {}, using default", key);
+
+                default
+            }
+        },
+        Err(_) => default,
+    }
+}
+
+fn list(key: &str, default: Vec<String>) -> Vec<String> {
+    match var(key) {
+        Ok(value) => value
+            .split(',')
+            .map(|item| item.trim().to_string())
+            .filter(|item| !item.is_empty())
+            .collect(),
+        Err(_) => default,
+    }
+}
+
+fn seconds(key: &str, default: Duration) -> Duration {
+    Duration::from_secs(number(key, default.as_secs()))
+}
+
+fn optional_seconds(key: &str, default: Option<Duration>) -> Option<Duration> {
+    match seconds(key, default.unwrap_or(Duration::ZERO)) {
+        duration if duration.is_zero() => None,
+        duration => Some(duration),
+    }
+}
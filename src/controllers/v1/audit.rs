@@ -0,0 +1,28 @@
+use lighter_common::prelude::*;
+
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::responses::v1::audit::{AuditLogPaginationRequest, AuditLogPaginationResponse};
+use crate::services;
+
+/// Paginate audit logs
+///
+/// Fail if current user doesn't have READ_AUDIT permission
+#[utoipa::path(
+    tag = "Audit",
+    security(("token" = [])),
+    params(AuditLogPaginationRequest),
+    responses(
+        AuditLogPaginationResponse,
+        BadRequest,
+        Unauthorized,
+        InternalServerError,
+    )
+)]
+#[get("/v1/audit")]
+pub async fn paginate(
+    auth: Auth,
+    db: Data<DatabaseConnection>,
+    QueryParam(request): QueryParam<AuditLogPaginationRequest>,
+) -> impl Responder {
+    services::v1::audit::paginate::paginate(&db, auth, request).await
+}
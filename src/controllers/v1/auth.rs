@@ -1,16 +1,33 @@
+use actix_web::http::header::USER_AGENT;
+use actix_web::web::Query;
+use actix_web::Either;
 use lighter_common::prelude::*;
 
+use crate::config::AuthConfig;
 use crate::middlewares::v1::auth::internal::Auth;
 use crate::middlewares::v1::auth::Authenticated as Cache;
-use crate::requests::v1::auth::LoginRequest;
-use crate::responses::v1::auth::Authenticated;
+use crate::middlewares::v1::auth::EmailVerification;
+use crate::requests::v1::auth::{
+    ApiKeyRequest, LoginRequest, PasswordStrengthRequest, PermissionCheckRequest,
+    VerifyEmailRequest,
+};
+use crate::responses::v1::auth::{
+    ApiKey, Authenticated, Me, PasswordStrength, Session, SlimAuthenticated, VerificationSent,
+};
+use crate::responses::v1::forbidden::Forbidden;
 use crate::services;
+use crate::services::v1::audit::{Action, AuditLogger};
+use crate::services::v1::notification::Notifications;
 
 /// Create a new session
 ///
+/// With `AUTH_LOGIN_EMBED_RBAC=false` the user comes without permissions and roles,
+/// shaped as `SlimAuthenticated`, fetch them from `/v1/auth/me` instead
+///
 /// Fail if:
-/// - email or username not found
-/// - password is incorrect
+/// - identifier isn't allowed by `AUTH_LOGIN_IDENTIFIER`
+/// - credentials are invalid, without telling whether the account exists
+/// - email is not verified and verified email is required
 #[utoipa::path(
     tag = "Auth",
     responses(
@@ -23,11 +40,45 @@ use crate::services;
 )]
 #[post("/login")]
 pub async fn login(
+    req: HttpRequest,
+    audit: AuditLogger,
     db: Data<DatabaseConnection>,
     cached: Data<Cache>,
+    config: Data<AuthConfig>,
     Json(request): Json<LoginRequest>,
 ) -> impl Responder {
-    services::v1::auth::login::login(&db, &cached, request).await
+    let user_agent = req
+        .headers()
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+    let ip_address = req.peer_addr().map(|addr| addr.ip().to_string());
+    let email_or_username = request.email_or_username.clone();
+    let result =
+        services::v1::auth::login::login(&db, &cached, &config, request, user_agent, ip_address)
+            .await;
+
+    match &result {
+        Ok(authenticated) => {
+            let id = authenticated.user.id;
+
+            audit
+                .log(Some(id), Action::Login, Some(("user", id)), None)
+                .await
+        }
+        Err(_) => {
+            let metadata = serde_json::json!({ "emailOrUsername": email_or_username });
+
+            audit
+                .log(None, Action::LoginFailed, None, Some(metadata))
+                .await
+        }
+    }
+
+    result.map(|authenticated| match config.login_embed_rbac {
+        true => Either::Left(authenticated),
+        false => Either::Right(SlimAuthenticated::from(authenticated)),
+    })
 }
 
 /// Get current session
@@ -35,6 +86,7 @@ pub async fn login(
 /// Fail if:
 /// - token not found
 /// - token is expired
+/// - user hasn't logged in within `AUTH_DORMANT_AFTER`, `ACCOUNT_DORMANT`
 #[utoipa::path(
     tag = "Auth",
     security(("token" = [])),
@@ -49,6 +101,53 @@ pub async fn authenticated(auth: Auth) -> impl Responder {
     services::v1::auth::authenticated::authenticated(auth).await
 }
 
+/// Get identity, permission codes and role codes of current user
+///
+/// Lighter alternative to `/user`, served from the cached session
+///
+/// Fail if:
+/// - token not found
+/// - token is expired
+#[utoipa::path(
+    tag = "Auth",
+    security(("token" = [])),
+    responses(
+        Me,
+        Unauthorized,
+        InternalServerError,
+    )
+)]
+#[get("/v1/auth/me")]
+pub async fn me(auth: Auth) -> impl Responder {
+    services::v1::auth::me::me(auth).await
+}
+
+/// Check many permissions of current user at once
+///
+/// Unknown permission codes are reported as not granted
+///
+/// Fail if:
+/// - token not found
+/// - token is expired
+#[utoipa::path(
+    tag = "Auth",
+    security(("token" = [])),
+    responses(
+        (
+            status = 200,
+            description = "Whether each permission is granted",
+            body = Object,
+            example = json!({ "READ_USER": true, "UPDATE_ROLE": false }),
+        ),
+        Unauthorized,
+        InternalServerError,
+    )
+)]
+#[post("/v1/auth/can")]
+pub async fn can(auth: Auth, Json(request): Json<PermissionCheckRequest>) -> impl Responder {
+    services::v1::auth::can::can(auth, request).await
+}
+
 /// Destroy current session
 ///
 /// Fail if:
@@ -66,8 +165,283 @@ pub async fn authenticated(auth: Auth) -> impl Responder {
 #[delete("/logout")]
 pub async fn logout(
     auth: Auth,
+    audit: AuditLogger,
+    db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+) -> impl Responder {
+    let id = auth.user.id;
+    let result = services::v1::auth::logout::logout(auth, &db, &cached).await;
+
+    if result.is_ok() {
+        audit
+            .log(Some(id), Action::Logout, Some(("user", id)), None)
+            .await;
+    }
+
+    result
+}
+
+/// Destroy every session of current user except the current one
+///
+/// Fail if:
+/// - token not found
+/// - token is expired
+#[utoipa::path(
+    tag = "Auth",
+    security(("token" = [])),
+    responses(
+        Success,
+        Unauthorized,
+        InternalServerError,
+    )
+)]
+#[post("/v1/auth/logout-others")]
+pub async fn logout_others(
+    auth: Auth,
+    audit: AuditLogger,
+    db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+) -> impl Responder {
+    let id = auth.user.id;
+    let result = services::v1::auth::logout_others::logout_others(auth, &db, &cached).await;
+
+    if result.is_ok() {
+        audit
+            .log(Some(id), Action::LogoutOthers, Some(("user", id)), None)
+            .await;
+    }
+
+    result
+}
+
+/// List active sessions of current user
+///
+/// Fail if:
+/// - token not found
+/// - token is expired
+#[utoipa::path(
+    tag = "Auth",
+    security(("token" = [])),
+    responses(
+        (status = 200, description = "Active sessions", body = [Session]),
+        Unauthorized,
+        InternalServerError,
+    )
+)]
+#[get("/v1/auth/sessions")]
+pub async fn sessions(auth: Auth, db: Data<DatabaseConnection>) -> impl Responder {
+    services::v1::auth::sessions::sessions(auth, &db).await
+}
+
+/// Revoke a session of current user, revoking the current one acts as logout
+///
+/// Fail if:
+/// - token not found
+/// - token is expired
+/// - session not found
+#[utoipa::path(
+    tag = "Auth",
+    security(("token" = [])),
+    responses(
+        Success,
+        Unauthorized,
+        NotFound,
+        InternalServerError,
+    )
+)]
+#[delete("/v1/auth/sessions/{id}")]
+pub async fn revoke_session(
+    auth: Auth,
+    audit: AuditLogger,
+    db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+    id: Path<String>,
+) -> impl Responder {
+    let user_id = auth.user.id;
+    let result =
+        services::v1::auth::revoke_session::revoke_session(auth, &db, &cached, id.into_inner())
+            .await;
+
+    if result.is_ok() {
+        audit
+            .log(
+                Some(user_id),
+                Action::RevokeSession,
+                Some(("user", user_id)),
+                None,
+            )
+            .await;
+    }
+
+    result
+}
+
+/// Revoke a token by the session id listed in `/v1/auth/sessions`
+///
+/// Own tokens can always be revoked, other users' tokens need REVOKE_TOKEN permission
+///
+/// Fail if:
+/// - token not found
+/// - token belongs to another user and current user doesn't have REVOKE_TOKEN permission,
+///   answered with 403
+#[utoipa::path(
+    tag = "Auth",
+    security(("token" = [])),
+    responses(
+        Success,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalServerError,
+    )
+)]
+#[delete("/v1/auth/token/{id}")]
+pub async fn revoke_token(
+    auth: Auth,
+    audit: AuditLogger,
     db: Data<DatabaseConnection>,
     cached: Data<Cache>,
+    id: Path<String>,
 ) -> impl Responder {
-    services::v1::auth::logout::logout(auth, &db, &cached).await
+    let actor = auth.user.id;
+    let result =
+        services::v1::auth::revoke_token::revoke_token(auth, &db, &cached, id.into_inner()).await;
+
+    if let Ok(Either::Left(owner)) = &result {
+        audit
+            .log(
+                Some(actor),
+                Action::RevokeToken,
+                Some(("user", *owner)),
+                None,
+            )
+            .await;
+    }
+
+    result.map(|result| match result {
+        Either::Left(_) => Either::Left(Success),
+        Either::Right(forbidden) => Either::Right(forbidden),
+    })
+}
+
+/// Create an api key for current user
+///
+/// The key is sent with the `X-Api-Key` header and is only shown once,
+/// its permissions are limited to the given scopes
+///
+/// Fail if:
+/// - token not found
+/// - token is expired
+/// - a scope isn't granted to current user
+#[utoipa::path(
+    tag = "Auth",
+    security(("token" = [])),
+    responses(
+        ApiKey,
+        Unauthorized,
+        Validation,
+        InternalServerError,
+    )
+)]
+#[post("/v1/auth/api-keys")]
+pub async fn create_api_key(
+    auth: Auth,
+    db: Data<DatabaseConnection>,
+    Json(request): Json<ApiKeyRequest>,
+) -> impl Responder {
+    services::v1::auth::create_api_key::create_api_key(auth, &db, request).await
+}
+
+/// Revoke an api key of current user
+///
+/// Fail if:
+/// - token not found
+/// - token is expired
+/// - api key not found
+#[utoipa::path(
+    tag = "Auth",
+    security(("token" = [])),
+    responses(
+        Success,
+        Unauthorized,
+        NotFound,
+        InternalServerError,
+    )
+)]
+#[delete("/v1/auth/api-keys/{id}")]
+pub async fn revoke_api_key(
+    auth: Auth,
+    db: Data<DatabaseConnection>,
+    id: Path<Uuid>,
+) -> impl Responder {
+    services::v1::auth::revoke_api_key::revoke_api_key(auth, &db, id.into_inner()).await
+}
+
+/// Send an email verification token to the email of current user
+///
+/// The token is delivered out-of-band by the configured notifier, only its expiry is returned
+///
+/// Fail if:
+/// - token not found
+/// - token is expired
+#[utoipa::path(
+    tag = "Auth",
+    security(("token" = [])),
+    responses(
+        VerificationSent,
+        Unauthorized,
+        InternalServerError,
+    )
+)]
+#[post("/v1/auth/send-verification")]
+pub async fn send_verification(
+    auth: Auth,
+    config: Data<AuthConfig>,
+    verification: Data<EmailVerification>,
+    notifications: Data<Notifications>,
+) -> impl Responder {
+    services::v1::auth::send_verification::send_verification(
+        auth,
+        &config,
+        &verification,
+        &notifications,
+    )
+    .await
+}
+
+/// Verify email address of the token owner
+///
+/// Verifying an already verified email is a no-op, a token issued by
+/// `POST /v1/user/{id}/email` replaces the email with the new one
+///
+/// Fail if:
+/// - verification token is invalid
+/// - verification token is expired
+/// - the new email was taken by another account meanwhile
+#[utoipa::path(
+    tag = "Auth",
+    params(VerifyEmailRequest),
+    responses(Success, BadRequest, NotFound, InternalServerError,)
+)]
+#[get("/v1/auth/verify-email")]
+pub async fn verify_email(
+    db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+    verification: Data<EmailVerification>,
+    Query(request): Query<VerifyEmailRequest>,
+) -> impl Responder {
+    services::v1::auth::verify_email::verify_email(&db, &cached, &verification, request).await
+}
+
+/// Estimate the strength of a password before submitting it
+///
+/// Scores range from 0 to 4, with warnings about guessable patterns and suggestions,
+/// `AUTH_PASSWORD_MIN_STRENGTH` rejects weaker passwords when storing users or passwords
+#[utoipa::path(
+    tag = "Auth",
+    responses(PasswordStrength, BadRequest, InternalServerError,)
+)]
+#[post("/v1/auth/password/strength")]
+pub async fn password_strength(Json(request): Json<PasswordStrengthRequest>) -> impl Responder {
+    services::v1::auth::password_strength::password_strength(request).await
 }
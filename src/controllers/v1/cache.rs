@@ -0,0 +1,58 @@
+use actix_web::Either;
+use lighter_common::prelude::*;
+
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+use crate::responses::v1::cache::{CacheCleared, CacheStats};
+use crate::responses::v1::forbidden::Forbidden;
+use crate::services;
+use crate::services::v1::audit::{Action, AuditLogger};
+
+/// Authenticated session cache statistics
+///
+/// Fail if current user doesn't have MANAGE_CACHE permission, answered with 403
+#[utoipa::path(
+    tag = "Cache",
+    security(("token" = [])),
+    responses(
+        CacheStats,
+        Unauthorized,
+        Forbidden,
+        InternalServerError,
+    )
+)]
+#[get("/v1/admin/cache/stats")]
+pub async fn stats(auth: Auth, cached: Data<Cache>) -> impl Responder {
+    services::v1::cache::stats::stats(auth, &cached).await
+}
+
+/// Clear the authenticated session cache
+///
+/// Sessions are reloaded from the database on their next request
+///
+/// Fail if current user doesn't have MANAGE_CACHE permission, answered with 403
+#[utoipa::path(
+    tag = "Cache",
+    security(("token" = [])),
+    responses(
+        CacheCleared,
+        Unauthorized,
+        Forbidden,
+        InternalServerError,
+    )
+)]
+#[post("/v1/admin/cache/clear")]
+pub async fn clear(auth: Auth, audit: AuditLogger, cached: Data<Cache>) -> impl Responder {
+    let actor = auth.user.id;
+    let result = services::v1::cache::clear::clear(auth, &cached).await;
+
+    if let Ok(Either::Left(cleared)) = &result {
+        let metadata = serde_json::json!({ "removed": cleared.removed });
+
+        audit
+            .log(Some(actor), Action::ClearCache, None, Some(metadata))
+            .await;
+    }
+
+    result
+}
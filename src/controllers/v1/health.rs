@@ -0,0 +1,26 @@
+use lighter_common::prelude::*;
+
+use crate::responses::v1::health::Health;
+use crate::services;
+
+/// Liveness probe, succeeds as long as the process is serving requests
+#[utoipa::path(tag = "Health", responses(Health))]
+#[get("/health/live")]
+pub async fn live() -> impl Responder {
+    services::v1::health::live::live().await
+}
+
+/// Readiness probe
+///
+/// Fail with 503 if the database is unreachable
+#[utoipa::path(
+    tag = "Health",
+    responses(
+        Health,
+        (status = 503, description = "Not ready", body = Health),
+    )
+)]
+#[get("/health/ready")]
+pub async fn ready(db: Data<DatabaseConnection>) -> impl Responder {
+    services::v1::health::ready::ready(&db).await
+}
@@ -0,0 +1,53 @@
+use actix_web::Either;
+use lighter_common::prelude::*;
+
+use crate::config::AuthConfig;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::responses::v1::auth::Authenticated;
+use crate::responses::v1::forbidden::Forbidden;
+use crate::services;
+use crate::services::v1::audit::{Action, AuditLogger};
+
+/// Issue a short-lived token acting as another user
+///
+/// The token expires after `AUTH_IMPERSONATION_TTL` and `/v1/auth/me` reports who
+/// is behind it in `impersonatedBy`
+///
+/// Fail if
+/// - user not found
+/// - current user doesn't have IMPERSONATE permission, answered with 403
+/// - current session is itself impersonated, answered with 403
+/// - user holds a permission listed in `AUTH_IMPERSONATION_PROTECTED`, answered with 403
+#[utoipa::path(
+    tag = "Impersonation",
+    security(("token" = [])),
+    responses(
+        Authenticated,
+        BadRequest,
+        Unauthorized,
+        Forbidden,
+        NotFound,
+        InternalServerError,
+    )
+)]
+#[post("/v1/admin/impersonate/{id}")]
+pub async fn impersonate(
+    auth: Auth,
+    audit: AuditLogger,
+    db: Data<DatabaseConnection>,
+    config: Data<AuthConfig>,
+    id: Path<Uuid>,
+) -> impl Responder {
+    let actor = auth.user.id;
+    let id = id.into_inner();
+    let result =
+        services::v1::impersonation::impersonate::impersonate(&db, &config, auth, id).await;
+
+    if let Ok(Either::Left(_)) = &result {
+        audit
+            .log(Some(actor), Action::Impersonate, Some(("user", id)), None)
+            .await;
+    }
+
+    result
+}
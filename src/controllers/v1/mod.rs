@@ -1,4 +1,8 @@
+pub mod audit;
 pub mod auth;
+pub mod cache;
+pub mod health;
+pub mod impersonation;
 pub mod permission;
 pub mod role;
 pub mod user;
@@ -1,6 +1,8 @@
 use lighter_common::prelude::*;
 
 use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+use crate::middlewares::v1::idempotency::Idempotency;
 use crate::requests::v1::permission::PermissionRequest;
 use crate::responses::v1::permission::{
     Permission, PermissionPaginationRequest, PermissionPaginationResponse,
@@ -8,6 +10,10 @@ use crate::responses::v1::permission::{
 use crate::services;
 
 /// Paginate permissions
+///
+/// Sortable by `code` (default) or `name` through `order`, in the `sort` direction
+///
+/// Fail with 400 if `order` isn't a sortable field
 #[utoipa::path(
     tag = "Permission",
     security(("token" = [])),
@@ -33,6 +39,9 @@ pub async fn paginate(
 /// Code field will take from name field and convert to uppercase and replace space with underscore
 ///
 /// Fail if code already exist
+///
+/// Retries sent with the same `Idempotency-Key` header replay the first response,
+/// reusing the key with a different body fails with 409
 #[utoipa::path(
     tag = "Permission",
     security(("token" = [])),
@@ -40,10 +49,20 @@ pub async fn paginate(
 )]
 #[post("/v1/permission")]
 pub async fn store(
+    req: HttpRequest,
     db: Data<DatabaseConnection>,
+    idempotency: Data<Idempotency>,
     Json(request): Json<PermissionRequest>,
 ) -> impl Responder {
-    services::v1::permission::store::store(&db, request).await
+    let fingerprint = Idempotency::fingerprint(&request);
+
+    idempotency
+        .run(
+            &req,
+            fingerprint,
+            services::v1::permission::store::store(&db, request),
+        )
+        .await
 }
 
 /// Show permission by id
@@ -70,10 +89,11 @@ pub async fn show(db: Data<DatabaseConnection>, id: Path<Uuid>) -> impl Responde
 #[put("/v1/permission/{id}")]
 pub async fn update(
     db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
     id: Path<Uuid>,
     Json(request): Json<PermissionRequest>,
 ) -> impl Responder {
-    services::v1::permission::update::update(&db, id.into_inner(), request).await
+    services::v1::permission::update::update(&db, &cached, id.into_inner(), request).await
 }
 
 /// Delete permission by id
@@ -85,6 +105,10 @@ pub async fn update(
     responses(Success, BadRequest, Unauthorized, NotFound, InternalServerError,)
 )]
 #[delete("/v1/permission/{id}")]
-pub async fn delete(db: Data<DatabaseConnection>, id: Path<Uuid>) -> impl Responder {
-    services::v1::permission::delete::delete(&db, id.into_inner()).await
+pub async fn delete(
+    db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+    id: Path<Uuid>,
+) -> impl Responder {
+    services::v1::permission::delete::delete(&db, &cached, id.into_inner()).await
 }
@@ -1,15 +1,29 @@
+use actix_web::web::Query;
 use lighter_common::prelude::*;
 
-use crate::requests::v1::role::RoleRequest;
-use crate::responses::v1::role::{Role, RolePaginationRequest, RolePaginationResponse};
+use crate::config::AuthConfig;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+use crate::middlewares::v1::idempotency::Idempotency;
+use crate::requests::v1::role::{RoleInclude, RoleRequest};
+use crate::responses::v1::role::{
+    Role, RolePaginationRequest, RolePaginationResponse, RoleWithPermissions,
+    RoleWithPermissionsPaginationResponse,
+};
 use crate::services;
 
 /// Paginate roles
+///
+/// Sortable by `code` (default) or `name` through `order`, in the `sort` direction.
+/// Permissions of each role are included when `include=permissions` is set
+///
+/// Fail with 400 if `order` isn't a sortable field
 #[utoipa::path(
     tag = "Role",
     security(("token" = [])),
+    params(RolePaginationRequest, RoleInclude),
     responses(
         RolePaginationResponse,
+        RoleWithPermissionsPaginationResponse,
         BadRequest,
         Unauthorized,
         InternalServerError,
@@ -19,15 +33,21 @@ use crate::services;
 pub async fn paginate(
     db: Data<DatabaseConnection>,
     QueryParam(request): QueryParam<RolePaginationRequest>,
+    Query(include): Query<RoleInclude>,
 ) -> impl Responder {
-    services::v1::role::paginate::paginate(&db, request).await
+    services::v1::role::paginate::paginate(&db, request, include).await
 }
 
 /// Store new role
 ///
 /// Code field will take from name field and convert to uppercase and replace space with underscore
 ///
-/// Fail if code already exist
+/// Fail if
+/// - code already exist
+/// - a permission is repeated or more than `AUTH_MAX_ASSIGNMENTS` are given
+///
+/// Retries sent with the same `Idempotency-Key` header replay the first response,
+/// reusing the key with a different body fails with 409
 #[utoipa::path(
     tag = "Role",
     security(("token" = [])),
@@ -35,28 +55,57 @@ pub async fn paginate(
 )]
 #[post("/v1/role")]
 pub async fn store(
+    req: HttpRequest,
     db: Data<DatabaseConnection>,
+    config: Data<AuthConfig>,
+    idempotency: Data<Idempotency>,
     Json(request): Json<RoleRequest>,
 ) -> impl Responder {
-    services::v1::role::store::store(&db, request).await
+    let fingerprint = Idempotency::fingerprint(&request);
+
+    idempotency
+        .run(
+            &req,
+            fingerprint,
+            services::v1::role::store::store(&db, &config, request),
+        )
+        .await
 }
 
 /// Show role by id
 ///
+/// Permissions of the role are included when `include=permissions` is set
+///
 /// Fail if role not found
 #[utoipa::path(
     tag = "Role",
     security(("token" = [])),
-    responses(Role, BadRequest, Unauthorized, NotFound, InternalServerError,)
+    params(RoleInclude),
+    responses(
+        Role,
+        RoleWithPermissions,
+        BadRequest,
+        Unauthorized,
+        NotFound,
+        InternalServerError,
+    )
 )]
 #[get("/v1/role/{id}")]
-pub async fn show(db: Data<DatabaseConnection>, id: Path<Uuid>) -> impl Responder {
-    services::v1::role::show::show(&db, id.into_inner()).await
+pub async fn show(
+    db: Data<DatabaseConnection>,
+    id: Path<Uuid>,
+    Query(include): Query<RoleInclude>,
+) -> impl Responder {
+    services::v1::role::show::show(&db, id.into_inner(), include).await
 }
 
 /// Update role by id
 ///
-/// Fail if role not found
+/// Fail if:
+/// - role not found
+/// - parent role not found
+/// - parent role would create a cycle in the hierarchy
+/// - a permission is repeated or more than `AUTH_MAX_ASSIGNMENTS` are given
 #[utoipa::path(
     tag = "Role",
     security(("token" = [])),
@@ -65,10 +114,12 @@ pub async fn show(db: Data<DatabaseConnection>, id: Path<Uuid>) -> impl Responde
 #[put("/v1/role/{id}")]
 pub async fn update(
     db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+    config: Data<AuthConfig>,
     id: Path<Uuid>,
     Json(request): Json<RoleRequest>,
 ) -> impl Responder {
-    services::v1::role::update::update(&db, id.into_inner(), request).await
+    services::v1::role::update::update(&db, &cached, &config, id.into_inner(), request).await
 }
 
 /// Delete role by id
@@ -80,6 +131,10 @@ pub async fn update(
     responses(Success, BadRequest, Unauthorized, NotFound, InternalServerError,)
 )]
 #[delete("/v1/role/{id}")]
-pub async fn delete(db: Data<DatabaseConnection>, id: Path<Uuid>) -> impl Responder {
-    services::v1::role::delete::delete(&db, id.into_inner()).await
+pub async fn delete(
+    db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+    id: Path<Uuid>,
+) -> impl Responder {
+    services::v1::role::delete::delete(&db, &cached, id.into_inner()).await
 }
@@ -1,17 +1,33 @@
+use actix_web::web::Query;
 use lighter_common::prelude::*;
 
+use crate::config::AuthConfig;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::{Authenticated as Cache, EmailVerification, OptionalAuth};
+use crate::middlewares::v1::idempotency::Idempotency;
 use crate::requests::v1::user::{
+    UserDeleteFilter, UserEmailRequest, UserPaginationFilter, UserPermissionsRequest,
     UserStoreRequest, UserUpdateGeneralInformationRequest, UserUpdatePasswordRequest,
 };
+use crate::responses::v1::auth::{SessionCount, VerificationSent};
+use crate::responses::v1::conflict::Conflict;
+use crate::responses::v1::user::bulk::BulkUserResponse;
 use crate::responses::v1::user::complete::UserWithPermissionAndRole;
+use crate::responses::v1::user::export::UserExport;
 use crate::responses::v1::user::simple::{UserPaginationRequest, UserPaginationResponse};
 use crate::services;
+use crate::services::v1::audit::{Action, AuditLogger};
+use crate::services::v1::notification::Notifications;
 
 /// Paginate users
+///
+/// Soft deleted users are excluded unless `include_deleted` is set
+///
+/// Fail if current user doesn't have READ_USER permission
 #[utoipa::path(
     tag = "User",
     security(("token" = [])),
-    params(UserPaginationRequest),
+    params(UserPaginationRequest, UserPaginationFilter),
     responses(
         UserPaginationResponse,
         BadRequest,
@@ -21,18 +37,30 @@ use crate::services;
 )]
 #[get("/v1/user")]
 pub async fn paginate(
+    auth: Auth,
     db: Data<DatabaseConnection>,
     QueryParam(request): QueryParam<UserPaginationRequest>,
+    Query(filter): Query<UserPaginationFilter>,
 ) -> impl Responder {
-    services::v1::user::paginate::paginate(&db, request).await
+    services::v1::user::paginate::paginate(&db, auth, request, filter).await
 }
 
 /// Store new user
 ///
+/// Usernames are case-insensitive and limited to ASCII letters, digits, `.`, `_` and `-`
+///
+/// Without `roles` the user gets `AUTH_DEFAULT_ROLES`, without `permissions` it gets
+/// `AUTH_DEFAULT_PERMISSIONS`, explicit assignments replace the defaults
+///
 /// Fail if
-/// - email already exist
-/// - username already exist
-/// - password is too short
+/// - email or username already exist, with 409 naming the `field`
+/// - username contains a disallowed character
+/// - password doesn't satisfy the password policy
+/// - a permission or role doesn't exist, the unknown ids are listed
+/// - more permissions or roles than `AUTH_MAX_ASSIGNMENTS` are given, duplicates count once
+///
+/// Retries sent with the same `Idempotency-Key` header replay the first response,
+/// reusing the key with a different body fails with 409
 #[utoipa::path(
     tag = "User",
     security(("token" = [])),
@@ -41,15 +69,51 @@ pub async fn paginate(
         BadRequest,
         Unauthorized,
         Validation,
+        Conflict,
         InternalServerError,
     ),
 )]
 #[post("/v1/user")]
 pub async fn store(
+    req: HttpRequest,
     db: Data<DatabaseConnection>,
+    config: Data<AuthConfig>,
+    idempotency: Data<Idempotency>,
     Json(request): Json<UserStoreRequest>,
 ) -> impl Responder {
-    services::v1::user::store::store(&db, request).await
+    let fingerprint = Idempotency::fingerprint(&request);
+
+    idempotency
+        .run(
+            &req,
+            fingerprint,
+            services::v1::user::store::store(&db, &config, request),
+        )
+        .await
+}
+
+/// Import many users at once
+///
+/// Every user is validated and stored like `POST /v1/user`, one failing doesn't
+/// stop the rest, the result of each one is reported by its index in the request
+///
+/// Fail if
+/// - current user doesn't have CREATE_USER permission
+/// - no users or more than `AUTH_MAX_BULK_USERS` are given
+#[utoipa::path(
+    tag = "User",
+    security(("token" = [])),
+    responses(BulkUserResponse, BadRequest, Unauthorized, Validation, InternalServerError,),
+)]
+#[post("/v1/user/bulk")]
+pub async fn bulk(
+    req: HttpRequest,
+    auth: Auth,
+    db: Data<DatabaseConnection>,
+    config: Data<AuthConfig>,
+    Json(requests): Json<Vec<UserStoreRequest>>,
+) -> impl Responder {
+    services::v1::user::bulk::bulk(&req, &db, &config, auth, requests).await
 }
 
 /// Find user by id
@@ -70,12 +134,55 @@ pub async fn show(db: Data<DatabaseConnection>, id: Path<Uuid>) -> impl Responde
     services::v1::user::show::show(&db, id.into_inner()).await
 }
 
+/// Export everything stored about a user by id
+///
+/// Includes the profile, roles, permissions, active sessions and audit entries,
+/// password hashes are never part of it
+///
+/// Fail if
+/// - user not found
+/// - current user isn't the user and doesn't have READ_USER permission
+#[utoipa::path(
+    tag = "User",
+    security(("token" = [])),
+    responses(UserExport, BadRequest, Unauthorized, NotFound, InternalServerError,),
+)]
+#[get("/v1/user/{id}/export")]
+pub async fn export(auth: Auth, db: Data<DatabaseConnection>, id: Path<Uuid>) -> impl Responder {
+    services::v1::user::export::export(&db, auth, id.into_inner()).await
+}
+
+/// Count active sessions of user by id
+///
+/// Expired tokens are left out, tokens without expiry are counted
+///
+/// Fail if
+/// - user not found
+/// - current user isn't the user and doesn't have READ_USER permission
+#[utoipa::path(
+    tag = "User",
+    security(("token" = [])),
+    responses(SessionCount, BadRequest, Unauthorized, NotFound, InternalServerError,),
+)]
+#[get("/v1/user/{id}/sessions/count")]
+pub async fn session_count(
+    auth: Auth,
+    db: Data<DatabaseConnection>,
+    id: Path<Uuid>,
+) -> impl Responder {
+    services::v1::user::session_count::session_count(&db, auth, id.into_inner()).await
+}
+
 /// Update general information user by id
 ///
+/// The email can't be changed here, use `POST /v1/user/{id}/email` which confirms it first
+///
 /// Fail if
 /// - user not found
-/// - email already exist
+/// - email differs from the current one
 /// - username already exist
+/// - username contains a disallowed character
+/// - more permissions or roles than `AUTH_MAX_ASSIGNMENTS` are given, duplicates count once
 #[utoipa::path(
     tag = "User",
     security(("token" = [])),
@@ -91,19 +198,24 @@ pub async fn show(db: Data<DatabaseConnection>, id: Path<Uuid>) -> impl Responde
 #[put("/v1/user/{id}")]
 pub async fn update_general_information(
     db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+    config: Data<AuthConfig>,
     id: Path<Uuid>,
     Json(request): Json<UserUpdateGeneralInformationRequest>,
 ) -> impl Responder {
-    services::v1::user::update_general_information::update(&db, id.into_inner(), request).await
+    let id = id.into_inner();
+
+    services::v1::user::update_general_information::update(&db, &cached, &config, id, request).await
 }
 
 /// Update user password by id
 ///
 /// Fail if
 /// - user not found
-/// - password is too short
+/// - password doesn't satisfy the password policy
 /// - password is not match with confirm password
 /// - old password is not match with current password
+/// - new password matches a recently used password
 #[utoipa::path(
     tag = "User",
     security(("token" = [])),
@@ -118,27 +230,235 @@ pub async fn update_general_information(
 )]
 #[put("/v1/user/{id}/password")]
 pub async fn update_password(
+    audit: AuditLogger,
     db: Data<DatabaseConnection>,
+    config: Data<AuthConfig>,
     id: Path<Uuid>,
     Json(request): Json<UserUpdatePasswordRequest>,
 ) -> impl Responder {
-    services::v1::user::update_password::update(&db, id.into_inner(), request).await
+    let id = id.into_inner();
+    let result = services::v1::user::update_password::update(&db, &config, id, request).await;
+
+    if result.is_ok() {
+        audit
+            .log(None, Action::UpdatePassword, Some(("user", id)), None)
+            .await;
+    }
+
+    result
+}
+
+/// Request an email change of user by id
+///
+/// The new email stays pending and the current one keeps working until the token,
+/// sent to the new address by the configured notifier, is confirmed through
+/// `GET /v1/auth/verify-email`, which swaps them and marks the email verified
+///
+/// Fail if
+/// - user not found
+/// - email is invalid, unchanged or already used by another account
+/// - current user isn't the user and doesn't have UPDATE_USER permission
+#[utoipa::path(
+    tag = "User",
+    security(("token" = [])),
+    responses(
+        VerificationSent,
+        NotFound,
+        BadRequest,
+        Unauthorized,
+        Validation,
+        InternalServerError,
+    ),
+)]
+#[post("/v1/user/{id}/email")]
+pub async fn change_email(
+    auth: Auth,
+    db: Data<DatabaseConnection>,
+    config: Data<AuthConfig>,
+    verification: Data<EmailVerification>,
+    notifications: Data<Notifications>,
+    id: Path<Uuid>,
+    Json(request): Json<UserEmailRequest>,
+) -> impl Responder {
+    let id = id.into_inner();
+
+    services::v1::user::change_email::change_email(
+        &db,
+        &config,
+        &verification,
+        &notifications,
+        auth,
+        id,
+        request,
+    )
+    .await
 }
 
 /// Delete user by id
 ///
-/// Fail if user not found
+/// Users are soft deleted, `purge=true` then erases an already deleted user
+/// with its sessions, api keys, password history and assignments
+///
+/// Fail if
+/// - user not found
+/// - purging a user that isn't soft deleted yet
+/// - purging without DELETE_USER permission
 #[utoipa::path(
     tag = "User",
     security(("token" = [])),
+    params(UserDeleteFilter),
     responses(
         Success,
         NotFound,
+        BadRequest,
         Unauthorized,
         InternalServerError,
     ),
 )]
 #[delete("/v1/user/{id}")]
-pub async fn delete(db: Data<DatabaseConnection>, id: Path<Uuid>) -> impl Responder {
-    services::v1::user::delete::delete(&db, id.into_inner()).await
+pub async fn delete(
+    OptionalAuth(auth): OptionalAuth,
+    audit: AuditLogger,
+    db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+    id: Path<Uuid>,
+    Query(filter): Query<UserDeleteFilter>,
+) -> impl Responder {
+    let id = id.into_inner();
+
+    if !filter.purge {
+        return services::v1::user::delete::delete(&db, &cached, id).await;
+    }
+
+    let actor = auth.as_ref().map(|auth| auth.user.id);
+    let result = services::v1::user::purge::purge(&db, &cached, auth, id).await;
+
+    if result.is_ok() {
+        audit
+            .log(actor, Action::PurgeUser, Some(("user", id)), None)
+            .await;
+    }
+
+    result
+}
+
+/// Restore soft deleted user by id
+///
+/// Restoring a user that isn't deleted returns the user unchanged
+///
+/// Fail if
+/// - user not found
+/// - current user doesn't have UPDATE_USER permission
+#[utoipa::path(
+    tag = "User",
+    security(("token" = [])),
+    responses(
+        UserWithPermissionAndRole,
+        NotFound,
+        Unauthorized,
+        InternalServerError,
+    ),
+)]
+#[post("/v1/user/{id}/restore")]
+pub async fn restore(auth: Auth, db: Data<DatabaseConnection>, id: Path<Uuid>) -> impl Responder {
+    services::v1::user::restore::restore(&db, auth, id.into_inner()).await
+}
+
+/// Grant permissions directly to user by id
+///
+/// Permissions already granted are left untouched, roles are not affected
+///
+/// Fail if
+/// - user not found
+/// - permission not found
+/// - more permissions than `AUTH_MAX_ASSIGNMENTS`
+/// - current user doesn't have UPDATE_USER permission
+#[utoipa::path(
+    tag = "User",
+    security(("token" = [])),
+    responses(
+        UserWithPermissionAndRole,
+        NotFound,
+        Unauthorized,
+        Validation,
+        InternalServerError,
+    ),
+)]
+#[post("/v1/user/{id}/permissions")]
+pub async fn assign_permissions(
+    auth: Auth,
+    audit: AuditLogger,
+    db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+    config: Data<AuthConfig>,
+    id: Path<Uuid>,
+    Json(request): Json<UserPermissionsRequest>,
+) -> impl Responder {
+    let actor = auth.user.id;
+    let id = id.into_inner();
+    let metadata = serde_json::json!({ "permissions": request.permissions });
+    let result = services::v1::user::assign_permissions::assign_permissions(
+        &db, &cached, &config, auth, id, request,
+    )
+    .await;
+
+    if result.is_ok() {
+        audit
+            .log(
+                Some(actor),
+                Action::AssignPermissions,
+                Some(("user", id)),
+                Some(metadata),
+            )
+            .await;
+    }
+
+    result
+}
+
+/// Revoke permissions granted directly to user by id
+///
+/// Permissions granted through roles are not affected
+///
+/// Fail if
+/// - user not found
+/// - current user doesn't have UPDATE_USER permission
+#[utoipa::path(
+    tag = "User",
+    security(("token" = [])),
+    responses(
+        UserWithPermissionAndRole,
+        NotFound,
+        Unauthorized,
+        InternalServerError,
+    ),
+)]
+#[delete("/v1/user/{id}/permissions")]
+pub async fn revoke_permissions(
+    auth: Auth,
+    audit: AuditLogger,
+    db: Data<DatabaseConnection>,
+    cached: Data<Cache>,
+    id: Path<Uuid>,
+    Json(request): Json<UserPermissionsRequest>,
+) -> impl Responder {
+    let actor = auth.user.id;
+    let id = id.into_inner();
+    let metadata = serde_json::json!({ "permissions": request.permissions });
+    let result =
+        services::v1::user::revoke_permissions::revoke_permissions(&db, &cached, auth, id, request)
+            .await;
+
+    if result.is_ok() {
+        audit
+            .log(
+                Some(actor),
+                Action::RevokePermissions,
+                Some(("user", id)),
+                Some(metadata),
+            )
+            .await;
+    }
+
+    result
 }
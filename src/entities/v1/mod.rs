@@ -2,6 +2,9 @@
 
 pub mod prelude;
 
+pub mod api_keys;
+pub mod audit_logs;
+pub mod password_histories;
 pub mod permission_role;
 pub mod permission_user;
 pub mod permissions;
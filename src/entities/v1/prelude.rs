@@ -1,5 +1,8 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.2
 
+pub use super::api_keys::Entity as ApiKeys;
+pub use super::audit_logs::Entity as AuditLogs;
+pub use super::password_histories::Entity as PasswordHistories;
 pub use super::permission_role::Entity as PermissionRole;
 pub use super::permission_user::Entity as PermissionUser;
 pub use super::permissions::Entity as Permissions;
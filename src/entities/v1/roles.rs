@@ -11,6 +11,7 @@ pub struct Model {
     #[sea_orm(unique)]
     pub code: String,
     pub name: String,
+    pub parent_id: Option<Uuid>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -19,6 +20,14 @@ pub enum Relation {
     PermissionRole,
     #[sea_orm(has_many = "super::role_user::Entity")]
     RoleUser,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "NoAction",
+        on_delete = "SetNull"
+    )]
+    SelfRef,
 }
 
 impl Related<super::permission_role::Entity> for Entity {
@@ -10,6 +10,13 @@ pub struct Model {
     pub id: Uuid,
     pub user_id: Uuid,
     pub expired_at: Option<DateTime>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: Option<DateTime>,
+    pub last_used_at: Option<DateTime>,
+    pub impersonated_by: Option<Uuid>,
+    #[sea_orm(unique)]
+    pub session_id: Uuid,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -19,10 +19,15 @@ pub struct Model {
     pub created_at: DateTime,
     pub updated_at: DateTime,
     pub deleted_at: Option<DateTime>,
+    pub last_login_at: Option<DateTime>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
+    #[sea_orm(has_many = "super::api_keys::Entity")]
+    ApiKeys,
+    #[sea_orm(has_many = "super::password_histories::Entity")]
+    PasswordHistories,
     #[sea_orm(has_many = "super::permission_user::Entity")]
     PermissionUser,
     #[sea_orm(has_many = "super::role_user::Entity")]
@@ -31,6 +36,18 @@ pub enum Relation {
     Tokens,
 }
 
+impl Related<super::api_keys::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ApiKeys.def()
+    }
+}
+
+impl Related<super::password_histories::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PasswordHistories.def()
+    }
+}
+
 impl Related<super::permission_user::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::PermissionUser.def()
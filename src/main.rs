@@ -4,6 +4,7 @@
 extern crate actix_web;
 
 pub mod api;
+pub mod config;
 pub mod controllers;
 pub mod entities;
 pub mod middlewares;
@@ -15,13 +16,25 @@ pub mod services;
 
 pub mod testing;
 
-use std::io::Error;
+use std::io::{Error, ErrorKind};
 
 use lighter_common::prelude::*;
 
+use crate::config::AuthConfig;
+
 #[actix::main]
 async fn main() -> Result<(), Error> {
     tracing::init();
+    config::load_secret_files(&["DATABASE_URL"]);
+
+    // fail before binding rather than misbehave on the first request
+    if let Err(errors) = AuthConfig::env().validate() {
+        for error in &errors {
+            tracing::error!("Invalid configuration: {}", error);
+        }
+
+        return Err(Error::new(ErrorKind::InvalidInput, errors.join(", ")));
+    }
 
     let server = Server::env().await;
 
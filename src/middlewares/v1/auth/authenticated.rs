@@ -1,25 +1,46 @@
 use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use lighter_common::prelude::*;
 
 use super::internal::Auth;
+use crate::responses::v1::cache::CacheStats;
 
 #[derive(Clone)]
 pub struct Authenticated {
     users: Arc<Mutex<BTreeMap<Uuid, Auth>>>,
+    hits: Arc<AtomicU64>,
+    misses: Arc<AtomicU64>,
 }
 
 impl Authenticated {
     pub fn new() -> Self {
         Self {
             users: Arc::new(Mutex::new(BTreeMap::new())),
+            hits: Arc::new(AtomicU64::new(0)),
+            misses: Arc::new(AtomicU64::new(0)),
         }
     }
 
     pub async fn get(&self, id: Uuid) -> Option<Auth> {
-        self.users.lock().unwrap().get(&id).cloned()
+        let auth = self.users.lock().unwrap().get(&id).cloned();
+
+        match auth {
+            Some(_) => self.hits.fetch_add(1, Ordering::Relaxed),
+            None => self.misses.fetch_add(1, Ordering::Relaxed),
+        };
+
+        auth
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            size: self.users.lock().unwrap().len() as u64,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
     }
 
     pub async fn set(&self, id: Uuid, auth: &Auth) {
@@ -30,6 +51,42 @@ impl Authenticated {
         self.users.lock().unwrap().remove(&id);
     }
 
+    /// Remove every cached session of the user, optionally keeping one token
+    pub async fn remove_by_user(&self, user_id: Uuid, except: Option<Uuid>) {
+        self.users
+            .lock()
+            .unwrap()
+            .retain(|id, auth| auth.user.id != user_id || Some(*id) == except);
+    }
+
+    /// Remove cached sessions holding any of the given roles
+    pub async fn remove_by_roles(&self, role_ids: &[Uuid]) {
+        self.users
+            .lock()
+            .unwrap()
+            .retain(|_, auth| !auth.roles.iter().any(|role| role_ids.contains(&role.id)));
+    }
+
+    /// Remove cached sessions holding the given permission
+    pub async fn remove_by_permission(&self, permission_id: Uuid) {
+        self.users.lock().unwrap().retain(|_, auth| {
+            !auth
+                .permissions
+                .iter()
+                .any(|permission| permission.id == permission_id)
+        });
+    }
+
+    /// Drop every cached session, returning how many were removed
+    pub async fn clear(&self) -> u64 {
+        let mut users = self.users.lock().unwrap();
+        let removed = users.len() as u64;
+
+        users.clear();
+
+        removed
+    }
+
     pub async fn remove_delay(&self, id: Uuid, delay: Duration) {
         let s = self.clone();
 
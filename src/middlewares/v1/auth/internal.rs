@@ -9,17 +9,37 @@ use sea_orm::EntityTrait;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use crate::entities::v1::{tokens, users};
+use crate::config::{AuthConfig, SessionMode};
+use crate::entities::v1::{api_keys, tokens, users};
 use crate::responses::v1::permission::Permission;
 use crate::responses::v1::role::Role;
 use crate::responses::v1::user::simple::User;
 
 use super::Authenticated;
+use crate::middlewares::v1::request_id::RequestId;
+
+/// Smallest extension written by a sliding session
+pub const SLIDE_THROTTLE: Duration = Duration::from_secs(60);
+
+/// Minimum age of `last_used_at` before a request records it again
+pub const TOUCH_THROTTLE: Duration = Duration::from_secs(60);
 
 #[derive(Clone, Deserialize, Serialize, ToSchema)]
 pub struct Auth {
     #[serde(skip)]
     pub id: Uuid,
+    /// Only kept for the dormant account check, never exposed
+    #[serde(skip)]
+    pub last_login_at: Option<NaiveDateTime>,
+    /// Expiry of the token, checked again when served from the cache
+    #[serde(skip)]
+    pub expired_at: Option<NaiveDateTime>,
+    /// Last recorded use of the token, see [`TOUCH_THROTTLE`]
+    #[serde(skip)]
+    pub last_used_at: Option<NaiveDateTime>,
+    /// User acting through an impersonation token, exposed through `/v1/auth/me`
+    #[serde(skip)]
+    pub impersonated_by: Option<Uuid>,
     #[schema()]
     pub user: User,
     #[schema()]
@@ -28,12 +48,30 @@ pub struct Auth {
     pub roles: Vec<Role>,
 }
 
+impl Auth {
+    /// Whether the permission is granted, directly or through a role
+    pub fn has_permission(&self, code: &str) -> bool {
+        self.permissions
+            .iter()
+            .any(|permission| permission.code == code)
+    }
+
+    pub fn has_any(&self, codes: &[&str]) -> bool {
+        codes.iter().any(|code| self.has_permission(code))
+    }
+
+    pub fn has_all(&self, codes: &[&str]) -> bool {
+        codes.iter().all(|code| self.has_permission(code))
+    }
+}
+
 impl FromRequest for Auth {
     type Error = Error;
     type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
 
     fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
         let start = std::time::Instant::now();
+        let request_id = RequestId::of(req);
 
         let db = match req.app_data::<Data<DatabaseConnection>>().cloned() {
             Some(db) => db,
@@ -57,6 +95,35 @@ impl FromRequest for Auth {
             }
         };
 
+        // apps without a config simply don't enforce the dormant account policy
+        let config = req.app_data::<Data<AuthConfig>>().cloned();
+
+        if let Some(key) = req.headers().get("X-Api-Key").cloned() {
+            return Box::pin(async move {
+                let key = match key.to_str() {
+                    Ok(key) => key,
+                    Err(e) => {
+                        tracing::error!("Failed to convert api key header to string");
+                        tracing::error!("Error: {}", e);
+
+                        return Err(BadRequest::new("Failed to convert api key to string").into());
+                    }
+                };
+
+                let auth = api_key(&db, key).await?;
+
+                dormant(config.as_deref(), &auth)?;
+
+                tracing::info!(
+                    "[{}] Authentication took: {:?}",
+                    request_id,
+                    start.elapsed()
+                );
+
+                Ok(auth)
+            });
+        }
+
         let header = match req.headers().get("Authorization").cloned() {
             Some(header) => header,
             None => {
@@ -88,6 +155,8 @@ impl FromRequest for Auth {
             });
         }
 
+        // malformed tokens are rejected before touching the cache or the database,
+        // well formed ones are random ids so looking them up leaks nothing useful
         let token = header.trim_start_matches("Bearer ");
         let token = match base58::decode(token) {
             Ok(token) => token,
@@ -96,7 +165,7 @@ impl FromRequest for Auth {
                     tracing::error!("Failed to decode token");
                     tracing::error!("Error: {}", e);
 
-                    Err(BadRequest::new("Failed to decode token").into())
+                    Err(Unauthorized::new("Invalid token").into())
                 });
             }
         };
@@ -108,19 +177,30 @@ impl FromRequest for Auth {
                     tracing::error!("Failed to convert token to uuid");
                     tracing::error!("Error: {}", e);
 
-                    Err(BadRequest::new("Failed to convert token to uuid").into())
+                    Err(Unauthorized::new("Invalid token").into())
                 });
             }
         };
 
         Box::pin(async move {
+            let db: &DatabaseConnection = &db;
+
             if let Some(auth) = authenticated.get(id).await {
-                tracing::info!("Authentication took: {:?}", start.elapsed());
+                expired(&auth)?;
+                dormant(config.as_deref(), &auth)?;
+
+                let auth = slide(db, &authenticated, config.as_deref(), auth).await;
+                let auth = touch(db, &authenticated, auth).await;
+
+                tracing::info!(
+                    "[{}] Authentication took: {:?}",
+                    request_id,
+                    start.elapsed()
+                );
 
                 return Ok(auth);
             }
 
-            let db: &DatabaseConnection = &db;
             let token = tokens::Entity::find_by_id(id)
                 .find_with_related(users::Entity)
                 .all(db)
@@ -145,10 +225,22 @@ impl FromRequest for Auth {
             }
 
             let user = user.first().cloned().unwrap();
+
+            // soft deleted users keep their tokens, they only work again once restored
+            if user.deleted_at.is_some() {
+                tracing::error!("User {} is deleted", user.id);
+
+                return Err(Unauthorized::new("Token not found").into());
+            }
+
             let permissions = user.permissions(db).await?;
             let roles = user.roles(db).await?;
             let auth = Auth {
                 id: token.id,
+                last_login_at: user.last_login_at,
+                expired_at: token.expired_at,
+                last_used_at: token.last_used_at,
+                impersonated_by: token.impersonated_by,
                 user: user.into(),
                 permissions: permissions
                     .into_iter()
@@ -162,13 +254,172 @@ impl FromRequest for Auth {
                 .remove_delay(id, Duration::from_secs(60 * 5))
                 .await;
 
-            tracing::info!("Authentication took: {:?}", start.elapsed());
+            dormant(config.as_deref(), &auth)?;
+
+            let auth = slide(db, &authenticated, config.as_deref(), auth).await;
+            let auth = touch(db, &authenticated, auth).await;
+
+            tracing::info!(
+                "[{}] Authentication took: {:?}",
+                request_id,
+                start.elapsed()
+            );
 
             Ok(auth)
         })
     }
 }
 
+/// Resolve the owner of an api key, limited to the permissions in its scopes
+///
+/// Roles of the owner aren't carried over, a key only grants its scopes
+async fn api_key(db: &DatabaseConnection, key: &str) -> Result<Auth, Error> {
+    let key = match api_keys::Model::find_by_key(db, key).await? {
+        Some(key) => key,
+        None => {
+            tracing::error!("Api key not found");
+
+            return Err(Unauthorized::new("Api key not found").into());
+        }
+    };
+
+    if let Some(expired_at) = key.expired_at {
+        if expired_at < now() {
+            tracing::error!("Api key expired");
+
+            return Err(Unauthorized::new("Api key expired").into());
+        }
+    }
+
+    let user = match users::Model::find_by_id(db, key.user_id).await {
+        Some(user) => user,
+        None => {
+            tracing::error!("Api key owner not found");
+
+            return Err(Unauthorized::new("Api key owner not found").into());
+        }
+    };
+
+    let scopes = key.scopes();
+    let permissions = user.permissions(db).await?;
+
+    // same throttle as tokens, a busy key shouldn't write on every request
+    let stale = match key.last_used_at {
+        Some(last_used_at) => last_used_at + TOUCH_THROTTLE <= now(),
+        None => true,
+    };
+
+    if stale {
+        if let Err(e) = key.touch(db).await {
+            tracing::error!("Failed to update api key last used at");
+            tracing::error!("Error: {}", e);
+        }
+    }
+
+    Ok(Auth {
+        id: key.id,
+        last_login_at: user.last_login_at,
+        expired_at: key.expired_at,
+        last_used_at: key.last_used_at,
+        impersonated_by: None,
+        user: user.into(),
+        permissions: permissions
+            .into_iter()
+            .filter(|permission| scopes.contains(&permission.code))
+            .map(|permission| permission.into())
+            .collect(),
+        roles: vec![],
+    })
+}
+
+fn expired(auth: &Auth) -> Result<(), Error> {
+    match auth.expired_at {
+        Some(expired_at) if expired_at < now() => {
+            tracing::error!("Token expired");
+
+            Err(Unauthorized::new("Token expired").into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// In sliding mode push the token expiry `session_idle_timeout` ahead of now,
+/// writing at most once per [`SLIDE_THROTTLE`] so most requests stay read-only
+async fn slide(
+    db: &DatabaseConnection,
+    authenticated: &Authenticated,
+    config: Option<&AuthConfig>,
+    mut auth: Auth,
+) -> Auth {
+    let config = match config {
+        Some(config) if config.session_mode == SessionMode::Sliding => config,
+        _ => return auth,
+    };
+
+    // impersonation tokens keep their short lifetime
+    if auth.impersonated_by.is_some() {
+        return auth;
+    }
+
+    // non-expiring tokens have nothing to slide
+    let expired_at = match auth.expired_at {
+        Some(expired_at) => expired_at,
+        None => return auth,
+    };
+
+    let next = now() + config.session_idle_timeout;
+
+    if next < expired_at + SLIDE_THROTTLE {
+        return auth;
+    }
+
+    if let Err(e) = tokens::Model::extend(db, auth.id, next).await {
+        tracing::error!("Failed to extend token expiry");
+        tracing::error!("Error: {}", e);
+
+        return auth;
+    }
+
+    auth.expired_at = Some(next);
+    authenticated.set(auth.id, &auth).await;
+
+    auth
+}
+
+/// Reject sessions of users who haven't logged in within `dormant_after`,
+/// logging in again records a fresh login and lifts it
+fn dormant(config: Option<&AuthConfig>, auth: &Auth) -> Result<(), Error> {
+    if config.is_some_and(|config| config.is_dormant(auth.last_login_at)) {
+        tracing::error!("Account {} is dormant", auth.user.id);
+
+        return Err(Unauthorized::new("ACCOUNT_DORMANT").into());
+    }
+
+    Ok(())
+}
+
+/// Record the use of the token, writing at most once per [`TOUCH_THROTTLE`]
+/// so requests served from the cache stay read-only
+async fn touch(db: &DatabaseConnection, authenticated: &Authenticated, mut auth: Auth) -> Auth {
+    if let Some(last_used_at) = auth.last_used_at {
+        if last_used_at + TOUCH_THROTTLE > now() {
+            return auth;
+        }
+    }
+
+    if let Err(e) = tokens::Model::touch(db, auth.id).await {
+        tracing::error!("Failed to update token last used at");
+        tracing::error!("Error: {}", e);
+
+        return auth;
+    }
+
+    auth.last_used_at = Some(now());
+    authenticated.set(auth.id, &auth).await;
+
+    auth
+}
+
 impl Responder for Auth {
     type Body = <Json<Self> as Responder>::Body;
 
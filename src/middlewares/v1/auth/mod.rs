@@ -1,5 +1,9 @@
 pub mod auth;
 pub mod authenticated;
 pub(crate) mod internal;
+pub mod optional;
+pub mod verification;
 
 pub use authenticated::Authenticated;
+pub use optional::OptionalAuth;
+pub use verification::EmailVerification;
@@ -0,0 +1,45 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use actix_web::dev::Payload;
+use actix_web::FromRequest;
+use lighter_common::prelude::*;
+
+use super::internal::Auth;
+
+/// Resolve the current session when a valid token or api key is present,
+/// yielding `None` instead of rejecting the request otherwise
+///
+/// Only credential failures fall back to `None`, server errors are still raised
+#[derive(Clone)]
+pub struct OptionalAuth(pub Option<Auth>);
+
+impl FromRequest for OptionalAuth {
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let headers = req.headers();
+
+        if !headers.contains_key("Authorization") && !headers.contains_key("X-Api-Key") {
+            return Box::pin(async move { Ok(Self(None)) });
+        }
+
+        let auth = Auth::from_request(req, payload);
+
+        Box::pin(async move {
+            match auth.await {
+                Ok(auth) => Ok(Self(Some(auth))),
+                Err(e) => {
+                    let e: actix_web::Error = e.into();
+
+                    if e.as_response_error().status_code().is_client_error() {
+                        Ok(Self(None))
+                    } else {
+                        Err(e)
+                    }
+                }
+            }
+        })
+    }
+}
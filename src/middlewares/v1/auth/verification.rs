@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use lighter_common::prelude::*;
+
+/// Pending email verification tokens, keyed by token id
+///
+/// A token issued for an email change carries the new address, which replaces
+/// the current one once the token is confirmed
+#[derive(Clone)]
+pub struct EmailVerification {
+    tokens: Arc<Mutex<BTreeMap<Uuid, (Uuid, Option<String>, NaiveDateTime)>>>,
+}
+
+impl EmailVerification {
+    pub fn new() -> Self {
+        Self {
+            tokens: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    pub async fn issue(&self, user_id: Uuid, lifetime: Duration) -> (Uuid, NaiveDateTime) {
+        let token = Uuid::new_v4();
+        let expired_at = now() + lifetime;
+        let mut tokens = self.tokens.lock().unwrap();
+
+        tokens.retain(|_, (_, _, expired_at)| *expired_at > now());
+        tokens.insert(token, (user_id, None, expired_at));
+
+        (token, expired_at)
+    }
+
+    /// Issue a token confirming `email` as the new address of the user,
+    /// earlier pending changes of the user are dropped
+    pub async fn issue_email_change(
+        &self,
+        user_id: Uuid,
+        email: String,
+        lifetime: Duration,
+    ) -> (Uuid, NaiveDateTime) {
+        let token = Uuid::new_v4();
+        let expired_at = now() + lifetime;
+        let mut tokens = self.tokens.lock().unwrap();
+
+        tokens.retain(|_, (id, email, expired_at)| {
+            *expired_at > now() && (*id != user_id || email.is_none())
+        });
+        tokens.insert(token, (user_id, Some(email), expired_at));
+
+        (token, expired_at)
+    }
+
+    pub async fn get(&self, token: Uuid) -> Option<(Uuid, Option<String>, NaiveDateTime)> {
+        self.tokens.lock().unwrap().get(&token).cloned()
+    }
+
+    pub async fn remove(&self, token: Uuid) {
+        self.tokens.lock().unwrap().remove(&token);
+    }
+}
@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use actix_web::body::to_bytes;
+use actix_web::http::header::{HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use actix_web::web::Bytes;
+use lighter_common::prelude::*;
+use serde::Serialize;
+
+pub const HEADER: &str = "idempotency-key";
+
+// 1 day
+pub const LIFETIME: Duration = Duration::from_secs(60 * 60 * 24);
+
+#[derive(Clone)]
+enum Entry {
+    /// Reserved by a request that is still running
+    Pending {
+        fingerprint: String,
+        expired_at: NaiveDateTime,
+    },
+    Done(Stored),
+}
+
+impl Entry {
+    fn fingerprint(&self) -> &str {
+        match self {
+            Self::Pending { fingerprint, .. } => fingerprint,
+            Self::Done(stored) => &stored.fingerprint,
+        }
+    }
+
+    fn expired_at(&self) -> NaiveDateTime {
+        match self {
+            Self::Pending { expired_at, .. } => *expired_at,
+            Self::Done(stored) => stored.expired_at,
+        }
+    }
+}
+
+/// Releases a pending key unless the response got stored, e.g. when the
+/// handler fails or the request is dropped midway
+struct Reservation<'a> {
+    responses: &'a Mutex<BTreeMap<String, Entry>>,
+    key: Option<String>,
+}
+
+impl Reservation<'_> {
+    fn complete(mut self, stored: Stored) {
+        if let Some(key) = self.key.take() {
+            self.responses
+                .lock()
+                .unwrap()
+                .insert(key, Entry::Done(stored));
+        }
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.responses.lock().unwrap().remove(&key);
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Stored {
+    fingerprint: String,
+    status: StatusCode,
+    content_type: Option<HeaderValue>,
+    body: Bytes,
+    expired_at: NaiveDateTime,
+}
+
+/// Successful responses of create requests sent with an `Idempotency-Key`
+///
+/// Keys are scoped to the caller's credentials, method and path, a retry
+/// with the same key and body replays the stored response instead of
+/// creating the resource again
+///
+/// The key is reserved before the handler runs, so a concurrent duplicate
+/// gets 409 instead of creating the resource twice
+#[derive(Clone)]
+pub struct Idempotency {
+    responses: Arc<Mutex<BTreeMap<String, Entry>>>,
+}
+
+impl Idempotency {
+    pub fn new() -> Self {
+        Self {
+            responses: Arc::new(Mutex::new(BTreeMap::new())),
+        }
+    }
+
+    pub fn fingerprint<T: Serialize>(request: &T) -> String {
+        serde_json::to_string(request).unwrap_or_default()
+    }
+
+    /// Run `handler` once per idempotency key
+    ///
+    /// Reusing a key with a different request body, or while the first request
+    /// is still running, responds with 409
+    pub async fn run<F, R>(
+        &self,
+        req: &HttpRequest,
+        fingerprint: String,
+        handler: F,
+    ) -> HttpResponse
+    where
+        F: Future<Output = R>,
+        R: Responder,
+    {
+        let key = match req.headers().get(HEADER).and_then(|v| v.to_str().ok()) {
+            Some(key) if !key.trim().is_empty() => format!(
+                "{} {} {} {}",
+                req.headers()
+                    .get(AUTHORIZATION)
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or_default(),
+                req.method(),
+                req.path(),
+                key.trim()
+            ),
+            _ => return handler.await.respond_to(req).map_into_boxed_body(),
+        };
+
+        // looked up and reserved under one lock, so only one request runs per key
+        let entry = {
+            let mut responses = self.responses.lock().unwrap();
+
+            responses.retain(|_, entry| entry.expired_at() > now());
+
+            let entry = responses.get(&key).cloned();
+
+            if entry.is_none() {
+                responses.insert(
+                    key.clone(),
+                    Entry::Pending {
+                        fingerprint: fingerprint.clone(),
+                        expired_at: now() + LIFETIME,
+                    },
+                );
+            }
+
+            entry
+        };
+
+        if let Some(entry) = entry {
+            if entry.fingerprint() != fingerprint {
+                return HttpResponse::Conflict().json(serde_json::json!({
+                    "message": "Idempotency-Key was already used with a different request",
+                }));
+            }
+
+            return match entry {
+                Entry::Pending { .. } => HttpResponse::Conflict().json(serde_json::json!({
+                    "message": "A request with this Idempotency-Key is still in progress",
+                })),
+                Entry::Done(stored) => Self::replay(&stored),
+            };
+        }
+
+        let reservation = Reservation {
+            responses: &self.responses,
+            key: Some(key),
+        };
+        let response = handler.await.respond_to(req).map_into_boxed_body();
+
+        // failures release the key, a retry runs the handler again
+        if !response.status().is_success() {
+            return response;
+        }
+
+        let status = response.status();
+        let content_type = response.headers().get(CONTENT_TYPE).cloned();
+        let body = match to_bytes(response.into_body()).await {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!("Failed to read response body");
+                tracing::error!("Error: {}", e);
+
+                return HttpResponse::InternalServerError().finish();
+            }
+        };
+        let stored = Stored {
+            fingerprint,
+            status,
+            content_type,
+            body,
+            expired_at: now() + LIFETIME,
+        };
+
+        reservation.complete(stored.clone());
+
+        Self::replay(&stored)
+    }
+
+    fn replay(stored: &Stored) -> HttpResponse {
+        let mut response = HttpResponse::build(stored.status);
+
+        if let Some(content_type) = &stored.content_type {
+            response.insert_header((CONTENT_TYPE, content_type.clone()));
+        }
+
+        response.body(stored.body.clone())
+    }
+}
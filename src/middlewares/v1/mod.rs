@@ -1 +1,4 @@
 pub mod auth;
+pub mod idempotency;
+pub mod request_id;
+pub mod version;
@@ -0,0 +1,108 @@
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::{FromRequest, HttpMessage};
+use lighter_common::prelude::*;
+
+pub const HEADER: &str = "x-request-id";
+
+/// Correlation id of the current request
+///
+/// Taken from the incoming `X-Request-Id` header, or a fresh uuid when it's
+/// absent, and echoed back on the response
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    /// Correlation id of the request, empty outside of [`RequestIdMiddleware`]
+    pub fn of(req: &HttpRequest) -> String {
+        req.extensions()
+            .get::<Self>()
+            .map(|id| id.0.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl FromRequest for RequestId {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(Ok(Self(Self::of(req))))
+    }
+}
+
+pub struct RequestIdMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestIdMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = RequestIdService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct RequestIdService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let id = req
+            .headers()
+            .get(HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty() && value.len() <= 128)
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        req.extensions_mut().insert(RequestId(id.clone()));
+
+        let method = req.method().clone();
+        let path = req.path().to_string();
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let mut response = service.call(req).await?;
+
+            tracing::info!(
+                "[{}] {} {} {}",
+                id,
+                method,
+                path,
+                response.status().as_u16()
+            );
+
+            if let Ok(value) = HeaderValue::from_str(&id) {
+                response
+                    .headers_mut()
+                    .insert(HeaderName::from_static(HEADER), value);
+            }
+
+            Ok(response)
+        })
+    }
+}
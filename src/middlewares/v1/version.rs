@@ -0,0 +1,125 @@
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use actix_web::dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::http::Method;
+use lighter_common::prelude::*;
+
+pub const HEADER: &str = "x-api-version";
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// When a deprecated endpoint stopped being recommended and when it goes away
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Deprecation {
+    pub since: NaiveDateTime,
+    pub sunset: Option<NaiveDateTime>,
+}
+
+impl Deprecation {
+    /// `Deprecation` header value, a structured date as in RFC 9745
+    pub fn deprecation(&self) -> String {
+        format!("@{}", self.since.and_utc().timestamp())
+    }
+
+    /// `Sunset` header value, an HTTP date as in RFC 8594
+    pub fn sunset(&self) -> Option<String> {
+        self.sunset
+            .map(|sunset| sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+    }
+}
+
+/// Tags every response with `X-Api-Version` and deprecated endpoints with
+/// `Deprecation` and `Sunset`
+///
+/// Endpoints are matched by method and route pattern, e.g. `/v1/user/{id}`
+#[derive(Clone, Default)]
+pub struct ApiVersionMiddleware {
+    deprecated: Arc<Vec<(Method, String, Deprecation)>>,
+}
+
+impl ApiVersionMiddleware {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the endpoint serving `method` on `pattern` as deprecated
+    pub fn deprecate(mut self, method: Method, pattern: &str, deprecation: Deprecation) -> Self {
+        Arc::make_mut(&mut self.deprecated).push((method, pattern.to_string(), deprecation));
+        self
+    }
+
+    fn find(&self, method: &Method, pattern: &str) -> Option<&Deprecation> {
+        self.deprecated
+            .iter()
+            .find(|(m, p, _)| m == method && p == pattern)
+            .map(|(_, _, deprecation)| deprecation)
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ApiVersionMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = ApiVersionService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ApiVersionService {
+            service: Rc::new(service),
+            middleware: self.clone(),
+        }))
+    }
+}
+
+pub struct ApiVersionService<S> {
+    service: Rc<S>,
+    middleware: ApiVersionMiddleware,
+}
+
+impl<S, B> Service<ServiceRequest> for ApiVersionService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let deprecation = req
+            .match_pattern()
+            .and_then(|pattern| self.middleware.find(req.method(), &pattern).cloned());
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let mut response = service.call(req).await?;
+            let headers = response.headers_mut();
+
+            headers.insert(
+                HeaderName::from_static(HEADER),
+                HeaderValue::from_static(VERSION),
+            );
+
+            if let Some(deprecation) = deprecation {
+                if let Ok(value) = HeaderValue::from_str(&deprecation.deprecation()) {
+                    headers.insert(HeaderName::from_static("deprecation"), value);
+                }
+
+                if let Some(Ok(value)) = deprecation.sunset().map(|s| HeaderValue::from_str(&s)) {
+                    headers.insert(HeaderName::from_static("sunset"), value);
+                }
+            }
+
+            Ok(response)
+        })
+    }
+}
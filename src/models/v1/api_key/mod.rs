@@ -0,0 +1,98 @@
+use std::sync::OnceLock;
+
+use lighter_common::{base58, prelude::*};
+use sea_orm::prelude::*;
+
+use crate::entities::v1::api_keys::{ActiveModel, Column, Entity, Model};
+
+/// Length of the public lookup part of a key
+pub const PREFIX_LENGTH: usize = 12;
+
+/// Verified against when no key matches, so unknown prefixes cost a hash too
+fn decoy() -> &'static String {
+    static DECOY: OnceLock<String> = OnceLock::new();
+
+    DECOY.get_or_init(|| Hash::make(Uuid::nil(), "decoy secret").to_string())
+}
+
+impl Model {
+    /// Create a key for the user, returning it along with the full key which
+    /// is never stored and can't be recovered later
+    pub async fn generate(
+        db: &DatabaseConnection,
+        user_id: Uuid,
+        scopes: Vec<String>,
+        expired_at: Option<NaiveDateTime>,
+    ) -> Result<(Self, String), DbErr> {
+        let id = Uuid::new_v4();
+        let prefix = base58::to_string(Uuid::new_v4())
+            .chars()
+            .take(PREFIX_LENGTH)
+            .collect::<String>();
+        let secret = base58::to_string(Uuid::new_v4());
+        let key = Model {
+            id,
+            user_id,
+            prefix: prefix.clone(),
+            hashed_secret: Hash::make(id, &secret).to_string(),
+            scopes: scopes.join(","),
+            expired_at,
+            last_used_at: None,
+            created_at: now(),
+        };
+
+        let key = ActiveModel::from(key).insert(db).await?;
+
+        Ok((key, format!("{}.{}", prefix, secret)))
+    }
+
+    /// Find the key matching `prefix.secret`, `None` if the secret doesn't match
+    pub async fn find_by_key(db: &DatabaseConnection, key: &str) -> Result<Option<Self>, DbErr> {
+        let (prefix, secret) = match key.trim().split_once('.') {
+            Some(parts) => parts,
+            None => return Ok(None),
+        };
+
+        // only the prefix is compared in the query, the secret is checked against its hash
+        let key = Entity::find()
+            .filter(Column::Prefix.eq(prefix))
+            .one(db)
+            .await?;
+
+        match key {
+            Some(key) if Hash::from(&key.hashed_secret).verify(key.id, &secret.to_string()) => {
+                Ok(Some(key))
+            }
+            Some(_) => Ok(None),
+            None => {
+                let _ = Hash::from(decoy()).verify(Uuid::nil(), &secret.to_string());
+
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn scopes(&self) -> Vec<String> {
+        self.scopes
+            .split(',')
+            .filter(|scope| !scope.is_empty())
+            .map(|scope| scope.to_string())
+            .collect()
+    }
+
+    pub async fn touch(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        Entity::update_many()
+            .col_expr(Column::LastUsedAt, Expr::value(now()))
+            .filter(Column::Id.eq(self.id))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        Entity::delete_by_id(self.id).exec(db).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use sea_orm::prelude::*;
+
+use crate::entities::v1::audit_logs::{ActiveModel, Model};
+use crate::responses::v1::audit::AuditLog;
+
+impl Model {
+    pub async fn store(&self, db: &DatabaseConnection) -> Result<Self, DbErr> {
+        ActiveModel::from(self.clone()).insert(db).await
+    }
+}
+
+impl Into<AuditLog> for Model {
+    fn into(self) -> AuditLog {
+        AuditLog {
+            id: self.id,
+            actor_user_id: self.actor_user_id,
+            action: self.action,
+            target_type: self.target_type,
+            target_id: self.target_id,
+            ip: self.ip,
+            metadata: self.metadata,
+            created_at: self.created_at,
+        }
+    }
+}
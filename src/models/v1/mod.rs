@@ -1,5 +1,7 @@
+pub mod api_key;
+pub mod audit_log;
+pub mod password_history;
 pub mod permission;
 pub mod role;
 pub mod token;
 pub mod user;
-
@@ -0,0 +1,45 @@
+use lighter_common::prelude::*;
+use sea_orm::prelude::*;
+use sea_orm::{QueryOrder, QuerySelect};
+
+use crate::entities::v1::password_histories::{Column, Entity, Model};
+
+impl Model {
+    pub async fn recent(
+        db: &DatabaseConnection,
+        user_id: Uuid,
+        limit: usize,
+    ) -> Result<Vec<Self>, DbErr> {
+        let query = Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_desc(Column::CreatedAt)
+            .limit(limit as u64);
+
+        query.all(db).await
+    }
+
+    pub async fn prune<C: ConnectionTrait>(
+        db: &C,
+        user_id: Uuid,
+        keep: usize,
+    ) -> Result<(), DbErr> {
+        let stale = Entity::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_desc(Column::CreatedAt)
+            .all(db)
+            .await?
+            .into_iter()
+            .skip(keep)
+            .map(|history| history.id)
+            .collect::<Vec<_>>();
+
+        if !stale.is_empty() {
+            Entity::delete_many()
+                .filter(Column::Id.is_in(stale))
+                .exec(db)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use lighter_common::prelude::*;
 use sea_orm::prelude::*;
 
 use crate::entities::v1::permissions::{ActiveModel, Column, Entity, Model};
+use crate::entities::v1::{permission_role, permission_user};
 use crate::responses::v1::permission::Permission;
 
 impl Model {
@@ -17,6 +18,21 @@ impl Model {
         }
     }
 
+    /// Look up permissions by code, codes that don't exist are skipped
+    pub async fn find_by_codes(
+        db: &DatabaseConnection,
+        codes: &[String],
+    ) -> Result<Vec<Self>, DbErr> {
+        if codes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Entity::find()
+            .filter(Column::Code.is_in(codes.to_vec()))
+            .all(db)
+            .await
+    }
+
     pub async fn code_exist<T: ToString>(db: &DatabaseConnection, code: T) -> bool {
         let code = code.to_string().replace(" ", "_").to_uppercase();
         let query = Entity::find().filter(Column::Code.eq(code)).count(db).await;
@@ -47,10 +63,26 @@ impl Model {
         model.update(db).await
     }
 
-    pub async fn delete(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
-        Entity::delete_by_id(self.id).exec(db).await?;
+    /// Delete the permission along with the rows granting it to roles and users
+    pub async fn delete(&self, db: &DatabaseConnection) -> Result<(), TransactionError<DbErr>> {
+        let id = self.id;
+
+        db.transaction(|db| {
+            Box::pin(async move {
+                permission_role::Entity::delete_many()
+                    .filter(permission_role::Column::PermissionId.eq(id))
+                    .exec(db)
+                    .await?;
+                permission_user::Entity::delete_many()
+                    .filter(permission_user::Column::PermissionId.eq(id))
+                    .exec(db)
+                    .await?;
+                Entity::delete_by_id(id).exec(db).await?;
 
-        Ok(())
+                Ok(())
+            })
+        })
+        .await
     }
 }
 
@@ -1,9 +1,15 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use lighter_common::prelude::*;
 use sea_orm::prelude::*;
 
 use crate::entities::v1::roles::{ActiveModel, Column, Entity, Model};
+use crate::entities::v1::{permission_role, permissions, role_user};
 use crate::responses::v1::role::Role;
 
+/// Maximum number of ancestors walked when resolving the role hierarchy
+pub const MAX_DEPTH: usize = 8;
+
 impl Model {
     pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> Result<Option<Self>, DbErr> {
         match Entity::find_by_id(id).one(db).await {
@@ -17,6 +23,21 @@ impl Model {
         }
     }
 
+    /// Look up roles by code, codes that don't exist are skipped
+    pub async fn find_by_codes(
+        db: &DatabaseConnection,
+        codes: &[String],
+    ) -> Result<Vec<Self>, DbErr> {
+        if codes.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Entity::find()
+            .filter(Column::Code.is_in(codes.to_vec()))
+            .all(db)
+            .await
+    }
+
     pub async fn code_exist<T: ToString>(db: &DatabaseConnection, code: T) -> bool {
         let code = code.to_string().replace(" ", "_").to_uppercase();
         let query = Entity::find().filter(Column::Code.eq(code)).count(db).await;
@@ -32,25 +53,222 @@ impl Model {
         }
     }
 
-    pub async fn store(&self, db: &DatabaseConnection) -> Result<Model, DbErr> {
-        ActiveModel::from(self.clone()).insert(db).await
+    pub async fn store(
+        &self,
+        db: &DatabaseConnection,
+        permissions: Vec<permissions::Model>,
+    ) -> Result<Model, TransactionError<DbErr>> {
+        db.transaction(|db| {
+            let role = self.clone();
+            let permissions = permissions
+                .iter()
+                .map(|permission| {
+                    permission_role::ActiveModel::from(permission_role::Model {
+                        id: Uuid::new_v4(),
+                        permission_id: permission.id,
+                        role_id: role.id,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            Box::pin(async move {
+                let role = ActiveModel::from(role).insert(db).await?;
+
+                if !permissions.is_empty() {
+                    permission_role::Entity::insert_many(permissions)
+                        .exec(db)
+                        .await?;
+                }
+
+                Ok(role)
+            })
+        })
+        .await
     }
 
     pub async fn update<T: ToString>(
         &self,
         db: &DatabaseConnection,
         name: T,
-    ) -> Result<Model, DbErr> {
-        let mut model = ActiveModel::from(self.clone());
-        model.name = Set(name.to_string());
+        parent_id: Option<Uuid>,
+        permissions: Vec<permissions::Model>,
+    ) -> Result<Model, TransactionError<DbErr>> {
+        let name = name.to_string();
+
+        db.transaction(|db| {
+            let role = self.clone();
+            let permissions = permissions
+                .iter()
+                .map(|permission| {
+                    permission_role::ActiveModel::from(permission_role::Model {
+                        id: Uuid::new_v4(),
+                        permission_id: permission.id,
+                        role_id: role.id,
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            Box::pin(async move {
+                let mut model = ActiveModel::from(role);
 
-        model.update(db).await
+                model.name = Set(name);
+                model.parent_id = Set(parent_id);
+
+                let role = model.update(db).await?;
+
+                permission_role::Entity::delete_many()
+                    .filter(permission_role::Column::RoleId.eq(role.id))
+                    .exec(db)
+                    .await?;
+
+                if !permissions.is_empty() {
+                    permission_role::Entity::insert_many(permissions)
+                        .exec(db)
+                        .await?;
+                }
+
+                Ok(role)
+            })
+        })
+        .await
+    }
+
+    pub async fn permissions(
+        &self,
+        db: &DatabaseConnection,
+    ) -> Result<Vec<permissions::Model>, DbErr> {
+        permissions::Entity::find()
+            .inner_join(permission_role::Entity)
+            .filter(permission_role::Column::RoleId.eq(self.id))
+            .all(db)
+            .await
     }
 
-    pub async fn delete(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
-        Entity::delete_by_id(self.id).exec(db).await?;
+    /// Get permissions of many roles at once, keyed by role id
+    pub async fn permissions_of(
+        db: &DatabaseConnection,
+        ids: Vec<Uuid>,
+    ) -> Result<BTreeMap<Uuid, Vec<permissions::Model>>, DbErr> {
+        let rows = permission_role::Entity::find()
+            .filter(permission_role::Column::RoleId.is_in(ids))
+            .find_also_related(permissions::Entity)
+            .all(db)
+            .await?;
+        let mut grouped = BTreeMap::<Uuid, Vec<permissions::Model>>::new();
+
+        for (pivot, permission) in rows {
+            if let Some(permission) = permission {
+                grouped.entry(pivot.role_id).or_default().push(permission);
+            }
+        }
+
+        Ok(grouped)
+    }
+
+    /// Check whether `parent_id` can become the parent of `id` without
+    /// creating a cycle or exceeding [`MAX_DEPTH`]
+    pub async fn is_valid_parent(
+        db: &DatabaseConnection,
+        id: Uuid,
+        parent_id: Uuid,
+    ) -> Result<bool, DbErr> {
+        let mut current = parent_id;
+
+        for _ in 0..MAX_DEPTH {
+            if current == id {
+                return Ok(false);
+            }
+
+            match Entity::find_by_id(current).one(db).await? {
+                Some(Model {
+                    parent_id: Some(parent_id),
+                    ..
+                }) => current = parent_id,
+                _ => return Ok(true),
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Get the given role ids along with the ids of all of their ancestors
+    pub async fn with_ancestors(
+        db: &DatabaseConnection,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<Uuid>, DbErr> {
+        let mut seen = ids.iter().cloned().collect::<BTreeSet<_>>();
+        let mut frontier = ids;
+
+        for _ in 0..MAX_DEPTH {
+            if frontier.is_empty() {
+                break;
+            }
+
+            frontier = Entity::find()
+                .filter(Column::Id.is_in(frontier))
+                .filter(Column::ParentId.is_not_null())
+                .all(db)
+                .await?
+                .into_iter()
+                .filter_map(|role| role.parent_id)
+                .filter(|id| seen.insert(*id))
+                .collect();
+        }
+
+        Ok(seen.into_iter().collect())
+    }
+
+    /// Get the given role ids along with the ids of all of their descendants
+    pub async fn with_descendants(
+        db: &DatabaseConnection,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<Uuid>, DbErr> {
+        let mut seen = ids.iter().cloned().collect::<BTreeSet<_>>();
+        let mut frontier = ids;
+
+        for _ in 0..MAX_DEPTH {
+            if frontier.is_empty() {
+                break;
+            }
+
+            frontier = Entity::find()
+                .filter(Column::ParentId.is_in(frontier))
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|role| role.id)
+                .filter(|id| seen.insert(*id))
+                .collect();
+        }
+
+        Ok(seen.into_iter().collect())
+    }
+
+    /// Delete the role along with its permission and member rows, child roles are detached
+    pub async fn delete(&self, db: &DatabaseConnection) -> Result<(), TransactionError<DbErr>> {
+        let id = self.id;
+
+        db.transaction(|db| {
+            Box::pin(async move {
+                permission_role::Entity::delete_many()
+                    .filter(permission_role::Column::RoleId.eq(id))
+                    .exec(db)
+                    .await?;
+                role_user::Entity::delete_many()
+                    .filter(role_user::Column::RoleId.eq(id))
+                    .exec(db)
+                    .await?;
+                Entity::update_many()
+                    .col_expr(Column::ParentId, Expr::value(Option::<Uuid>::None))
+                    .filter(Column::ParentId.eq(id))
+                    .exec(db)
+                    .await?;
+                Entity::delete_by_id(id).exec(db).await?;
 
-        Ok(())
+                Ok(())
+            })
+        })
+        .await
     }
 }
 
@@ -60,6 +278,7 @@ impl Into<Role> for Model {
             id: self.id,
             code: self.code,
             name: self.name,
+            parent_id: self.parent_id,
         }
     }
 }
@@ -70,6 +289,7 @@ impl Into<Role> for &Model {
             id: self.id,
             code: self.code.clone(),
             name: self.name.clone(),
+            parent_id: self.parent_id,
         }
     }
 }
@@ -1,5 +1,6 @@
 use lighter_common::prelude::*;
 use sea_orm::prelude::*;
+use sea_orm::QueryOrder;
 
 use crate::entities::v1::tokens::{ActiveModel, Column, Entity, Model};
 use crate::entities::v1::users;
@@ -30,6 +31,17 @@ impl Model {
         ActiveModel::from(self.clone()).insert(db).await
     }
 
+    /// Token behind a session handle, the handle is safe to expose unlike the token id
+    pub async fn find_by_session(
+        db: &DatabaseConnection,
+        session_id: Uuid,
+    ) -> Result<Option<Self>, DbErr> {
+        Entity::find()
+            .filter(Column::SessionId.eq(session_id))
+            .one(db)
+            .await
+    }
+
     pub async fn delete(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
         Entity::delete_by_id(self.id).exec(db).await?;
 
@@ -44,4 +56,70 @@ impl Model {
 
         Ok(())
     }
+
+    /// Delete a single token, leaving the other sessions of its user alone
+    pub async fn logout_session(db: &DatabaseConnection, id: Uuid) -> Result<(), DbErr> {
+        Entity::delete_by_id(id).exec(db).await?;
+
+        Ok(())
+    }
+
+    pub async fn logout_others(
+        db: &DatabaseConnection,
+        user_id: Uuid,
+        keep: Uuid,
+    ) -> Result<(), DbErr> {
+        Entity::delete_many()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Id.ne(keep))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Tokens of the user that haven't expired, non-expiring ones included
+    fn active_query(user_id: Uuid) -> Select<Entity> {
+        Entity::find().filter(Column::UserId.eq(user_id)).filter(
+            Condition::any()
+                .add(Column::ExpiredAt.gt(now()))
+                .add(Column::ExpiredAt.is_null()),
+        )
+    }
+
+    pub async fn active(db: &DatabaseConnection, user_id: Uuid) -> Result<Vec<Self>, DbErr> {
+        Self::active_query(user_id)
+            .order_by_desc(Column::CreatedAt)
+            .all(db)
+            .await
+    }
+
+    pub async fn count_active(db: &DatabaseConnection, user_id: Uuid) -> Result<u64, DbErr> {
+        Self::active_query(user_id).count(db).await
+    }
+
+    /// Move the expiry of a token, used by sliding sessions
+    pub async fn extend(
+        db: &DatabaseConnection,
+        id: Uuid,
+        expired_at: NaiveDateTime,
+    ) -> Result<(), DbErr> {
+        Entity::update_many()
+            .col_expr(Column::ExpiredAt, Expr::value(expired_at))
+            .filter(Column::Id.eq(id))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn touch(db: &DatabaseConnection, id: Uuid) -> Result<(), DbErr> {
+        Entity::update_many()
+            .col_expr(Column::LastUsedAt, Expr::value(now()))
+            .filter(Column::Id.eq(id))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
 }
@@ -1,14 +1,73 @@
+use std::collections::BTreeSet;
+
 use lighter_common::prelude::*;
 use sea_orm::prelude::*;
-use sea_orm::QuerySelect;
+use sea_orm::{QuerySelect, SqlErr};
 
 use crate::entities::v1::users::{ActiveModel, Column, Entity, Model};
 use crate::entities::v1::{
-    permission_role, permission_user, permissions, role_user, roles, tokens,
+    api_keys, password_histories, permission_role, permission_user, permissions, role_user, roles,
+    tokens,
 };
 use crate::responses::v1::user::simple::User;
 
 impl Model {
+    /// Usernames are case-insensitive, they're always stored and looked up lowercased
+    pub fn normalize_username<T: ToString>(username: T) -> String {
+        username.to_string().trim().to_lowercase()
+    }
+
+    /// Only ASCII letters, digits, `.`, `_` and `-` are allowed, which rules out
+    /// zero-width characters and non-ASCII lookalikes (e.g. Cyrillic `а` for `a`)
+    ///
+    /// Must be checked before [`Model::normalize_username`], lowercasing some
+    /// non-ASCII characters (e.g. Kelvin sign) yields ASCII letters
+    pub fn is_valid_username(username: &str) -> bool {
+        username
+            .trim()
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+    }
+
+    /// Emails are compared case-insensitively, with `canonicalize_gmail` dots
+    /// and `+tag` are also dropped from the local part of gmail addresses
+    pub fn normalize_email<T: ToString>(email: T, canonicalize_gmail: bool) -> String {
+        let email = email.to_string().trim().to_lowercase();
+
+        if !canonicalize_gmail {
+            return email;
+        }
+
+        match email.rsplit_once('@') {
+            Some((local, domain @ ("gmail.com" | "googlemail.com"))) => {
+                let local = local.split('+').next().unwrap_or(local).replace('.', "");
+
+                format!("{}@{}", local, domain)
+            }
+            _ => email,
+        }
+    }
+
+    /// Loose shape check, a single `@` between a non-empty local part and domain
+    pub fn is_valid_email(email: &str) -> bool {
+        match email.trim().split_once('@') {
+            Some((local, domain)) => {
+                !local.is_empty()
+                    && !domain.is_empty()
+                    && !domain.contains('@')
+                    && !email.trim().chars().any(char::is_whitespace)
+            }
+            None => false,
+        }
+    }
+
+    /// Drop repeated ids, keeping the first occurrence of each in order
+    pub fn unique_ids(ids: &[Uuid]) -> Vec<Uuid> {
+        let mut seen = BTreeSet::new();
+
+        ids.iter().copied().filter(|id| seen.insert(*id)).collect()
+    }
+
     pub async fn find_by_id(db: &DatabaseConnection, id: Uuid) -> Option<Self> {
         let query = Entity::find()
             .filter(Column::Id.eq(id))
@@ -25,9 +84,21 @@ impl Model {
         }
     }
 
+    pub async fn find_by_id_with_deleted(db: &DatabaseConnection, id: Uuid) -> Option<Self> {
+        match Entity::find_by_id(id).one(db).await {
+            Ok(user) => user,
+            Err(e) => {
+                tracing::error!("Failed to find user by id");
+                tracing::error!("Error: {}", e);
+
+                None
+            }
+        }
+    }
+
     pub async fn find_by_email<T: ToString>(db: &DatabaseConnection, email: T) -> Option<Self> {
         let query = Entity::find()
-            .filter(Column::Email.eq(email.to_string()))
+            .filter(Column::Email.eq(Self::normalize_email(email, false)))
             .filter(Column::DeletedAt.is_null());
 
         match query.one(db).await {
@@ -93,6 +164,7 @@ impl Model {
                     .add(Column::Username.eq(email_or_username.to_string()))
                     .add(Column::Email.eq(email_or_username.to_string())),
             )
+            .filter(Column::DeletedAt.is_null())
             .count(db);
 
         query.await.unwrap_or(0) > 0
@@ -100,15 +172,27 @@ impl Model {
 
     pub async fn email_exists<T: ToString>(db: &DatabaseConnection, email: T) -> bool {
         let query = Entity::find()
-            .filter(Column::Email.eq(email.to_string()))
+            .filter(Column::Email.eq(Self::normalize_email(email, false)))
             .count(db);
 
         query.await.unwrap_or(0) > 0
     }
 
+    /// Field whose unique constraint `e` violates, `email` or `username`
+    pub fn unique_violation(e: &DbErr) -> Option<&'static str> {
+        let message = match e.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(message)) => message.to_lowercase(),
+            _ => return None,
+        };
+
+        ["email", "username"]
+            .into_iter()
+            .find(|field| message.contains(field))
+    }
+
     pub async fn username_exists<T: ToString>(db: &DatabaseConnection, username: T) -> bool {
         let query = Entity::find()
-            .filter(Column::Username.eq(username.to_string()))
+            .filter(Column::Username.eq(Self::normalize_username(username)))
             .count(db);
 
         query.await.unwrap_or(0) > 0
@@ -122,8 +206,10 @@ impl Model {
     ) -> Result<Self, TransactionError<DbErr>> {
         db.transaction(|db| {
             let user = self.clone();
+            let mut seen = BTreeSet::new();
             let permissions = permissions
                 .iter()
+                .filter(|permission| seen.insert(permission.id))
                 .map(|permission| {
                     let permission_user = permission_user::Model {
                         id: Uuid::new_v4(),
@@ -134,8 +220,10 @@ impl Model {
                     permission_user::ActiveModel::from(permission_user)
                 })
                 .collect::<Vec<_>>();
+            let mut seen = BTreeSet::new();
             let roles = roles
                 .iter()
+                .filter(|role| seen.insert(role.id))
                 .map(|role| {
                     let role_user = role_user::Model {
                         id: Uuid::new_v4(),
@@ -166,6 +254,94 @@ impl Model {
         .await
     }
 
+    /// Like [`Model::store`] with permissions and roles given by code, resolved in the
+    /// same transaction so an unknown code fails the whole store without partial writes
+    pub async fn store_with_codes(
+        &self,
+        db: &DatabaseConnection,
+        permission_codes: &[&str],
+        role_codes: &[&str],
+    ) -> Result<Self, TransactionError<DbErr>> {
+        let permission_codes = permission_codes
+            .iter()
+            .map(|code| code.to_string())
+            .collect::<BTreeSet<_>>();
+        let role_codes = role_codes
+            .iter()
+            .map(|code| code.to_string())
+            .collect::<BTreeSet<_>>();
+
+        db.transaction(|db| {
+            let user = self.clone();
+
+            Box::pin(async move {
+                let permissions = permissions::Entity::find()
+                    .filter(permissions::Column::Code.is_in(permission_codes.clone()))
+                    .all(db)
+                    .await?;
+                let roles = roles::Entity::find()
+                    .filter(roles::Column::Code.is_in(role_codes.clone()))
+                    .all(db)
+                    .await?;
+                let mut unknown = vec![];
+
+                for code in &permission_codes {
+                    if !permissions
+                        .iter()
+                        .any(|permission| permission.code == *code)
+                    {
+                        unknown.push(format!("Permission {} does not exist", code));
+                    }
+                }
+
+                for code in &role_codes {
+                    if !roles.iter().any(|role| role.code == *code) {
+                        unknown.push(format!("Role {} does not exist", code));
+                    }
+                }
+
+                if !unknown.is_empty() {
+                    return Err(DbErr::RecordNotFound(unknown.join(", ")));
+                }
+
+                let user = ActiveModel::from(user).insert(db).await?;
+                let permissions = permissions
+                    .iter()
+                    .map(|permission| {
+                        permission_user::ActiveModel::from(permission_user::Model {
+                            id: Uuid::new_v4(),
+                            permission_id: permission.id,
+                            user_id: user.id,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                let roles = roles
+                    .iter()
+                    .map(|role| {
+                        role_user::ActiveModel::from(role_user::Model {
+                            id: Uuid::new_v4(),
+                            role_id: role.id,
+                            user_id: user.id,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                if !permissions.is_empty() {
+                    permission_user::Entity::insert_many(permissions)
+                        .exec(db)
+                        .await?;
+                }
+
+                if !roles.is_empty() {
+                    role_user::Entity::insert_many(roles).exec(db).await?;
+                }
+
+                Ok(user)
+            })
+        })
+        .await
+    }
+
     pub async fn update_general_information<Name, Email, Username>(
         &self,
         db: &DatabaseConnection,
@@ -189,8 +365,10 @@ impl Model {
 
         db.transaction(|db| {
             let user = self.clone();
+            let mut seen = BTreeSet::new();
             let permissions = permissions
                 .iter()
+                .filter(|permission| seen.insert(permission.id))
                 .map(|permission| {
                     permission_user::ActiveModel::from(permission_user::Model {
                         id: Uuid::new_v4(),
@@ -199,8 +377,10 @@ impl Model {
                     })
                 })
                 .collect::<Vec<_>>();
+            let mut seen = BTreeSet::new();
             let roles = roles
                 .iter()
+                .filter(|role| seen.insert(role.id))
                 .map(|role| {
                     role_user::ActiveModel::from(role_user::Model {
                         id: Uuid::new_v4(),
@@ -247,14 +427,63 @@ impl Model {
         .await
     }
 
+    /// Replace the password, keeping the previous hash in the last `history` entries
     pub async fn update_password(
         &self,
         db: &DatabaseConnection,
         password: Hash,
+        history: usize,
+    ) -> Result<Self, TransactionError<DbErr>> {
+        let password = password.to_string();
+
+        db.transaction(|db| {
+            let user = self.clone();
+            let previous = password_histories::ActiveModel::from(password_histories::Model {
+                id: Uuid::new_v4(),
+                user_id: user.id,
+                password: user.password.clone(),
+                created_at: now(),
+            });
+
+            Box::pin(async move {
+                if history > 0 {
+                    previous.insert(db).await?;
+
+                    password_histories::Model::prune(db, user.id, history).await?;
+                }
+
+                let mut model = ActiveModel::from(user);
+
+                model.password = Set(password);
+                model.updated_at = Set(now());
+                model.update(db).await
+            })
+        })
+        .await
+    }
+
+    pub async fn mark_email_verified(&self, db: &DatabaseConnection) -> Result<Self, DbErr> {
+        if self.email_verified_at.is_some() {
+            return Ok(self.clone());
+        }
+
+        let mut model = ActiveModel::from(self.clone());
+
+        model.email_verified_at = Set(Some(now()));
+        model.updated_at = Set(now());
+        model.update(db).await
+    }
+
+    /// Replace the email with a confirmed one, marking it verified
+    pub async fn change_email<T: ToString>(
+        &self,
+        db: &DatabaseConnection,
+        email: T,
     ) -> Result<Self, DbErr> {
         let mut model = ActiveModel::from(self.clone());
 
-        model.password = Set(password.to_string());
+        model.email = Set(email.to_string());
+        model.email_verified_at = Set(Some(now()));
         model.updated_at = Set(now());
         model.update(db).await
     }
@@ -266,10 +495,63 @@ impl Model {
         model.update(db).await
     }
 
+    /// Erase the user along with its sessions, api keys, password history and assignments
+    pub async fn purge(&self, db: &DatabaseConnection) -> Result<(), TransactionError<DbErr>> {
+        let id = self.id;
+
+        db.transaction(|db| {
+            Box::pin(async move {
+                tokens::Entity::delete_many()
+                    .filter(tokens::Column::UserId.eq(id))
+                    .exec(db)
+                    .await?;
+                api_keys::Entity::delete_many()
+                    .filter(api_keys::Column::UserId.eq(id))
+                    .exec(db)
+                    .await?;
+                password_histories::Entity::delete_many()
+                    .filter(password_histories::Column::UserId.eq(id))
+                    .exec(db)
+                    .await?;
+                permission_user::Entity::delete_many()
+                    .filter(permission_user::Column::UserId.eq(id))
+                    .exec(db)
+                    .await?;
+                role_user::Entity::delete_many()
+                    .filter(role_user::Column::UserId.eq(id))
+                    .exec(db)
+                    .await?;
+                Entity::delete_by_id(id).exec(db).await?;
+
+                Ok(())
+            })
+        })
+        .await
+    }
+
+    pub async fn restore(&self, db: &DatabaseConnection) -> Result<Self, DbErr> {
+        if self.deleted_at.is_none() {
+            return Ok(self.clone());
+        }
+
+        let mut model = ActiveModel::from(self.clone());
+
+        model.deleted_at = Set(None);
+        model.updated_at = Set(now());
+        model.update(db).await
+    }
+
     pub async fn permissions(
         &self,
         db: &DatabaseConnection,
     ) -> Result<Vec<permissions::Model>, DbErr> {
+        let roles = self
+            .roles(db)
+            .await?
+            .into_iter()
+            .map(|role| role.id)
+            .collect();
+        let roles = roles::Model::with_ancestors(db, roles).await?;
         let query = permissions::Entity::find()
             .join(
                 JoinType::LeftJoin,
@@ -279,19 +561,74 @@ impl Model {
                 JoinType::LeftJoin,
                 permissions::Relation::PermissionRole.def(),
             )
-            .join(JoinType::LeftJoin, permission_role::Relation::Roles.def())
-            .join(JoinType::LeftJoin, roles::Relation::RoleUser.def())
             .filter(permissions::Column::Id.is_not_null())
             .filter(
                 Condition::any()
                     .add(permission_user::Column::UserId.eq(self.id))
-                    .add(role_user::Column::UserId.eq(self.id)),
+                    .add(permission_role::Column::RoleId.is_in(roles)),
             )
             .group_by(permissions::Column::Id);
 
         query.all(db).await
     }
 
+    /// Grant permissions directly to the user, skipping ones already granted
+    pub async fn assign_permissions(
+        &self,
+        db: &DatabaseConnection,
+        permissions: Vec<Uuid>,
+    ) -> Result<(), TransactionError<DbErr>> {
+        let user_id = self.id;
+
+        db.transaction(|db| {
+            Box::pin(async move {
+                let permissions = permissions.into_iter().collect::<BTreeSet<_>>();
+                let existing = permission_user::Entity::find()
+                    .filter(permission_user::Column::UserId.eq(user_id))
+                    .filter(permission_user::Column::PermissionId.is_in(permissions.clone()))
+                    .all(db)
+                    .await?
+                    .into_iter()
+                    .map(|permission| permission.permission_id)
+                    .collect::<BTreeSet<_>>();
+                let permissions = permissions
+                    .difference(&existing)
+                    .map(|permission_id| {
+                        permission_user::ActiveModel::from(permission_user::Model {
+                            id: Uuid::new_v4(),
+                            permission_id: *permission_id,
+                            user_id,
+                        })
+                    })
+                    .collect::<Vec<_>>();
+
+                if !permissions.is_empty() {
+                    permission_user::Entity::insert_many(permissions)
+                        .exec(db)
+                        .await?;
+                }
+
+                Ok(())
+            })
+        })
+        .await
+    }
+
+    /// Revoke permissions granted directly to the user
+    pub async fn revoke_permissions(
+        &self,
+        db: &DatabaseConnection,
+        permissions: Vec<Uuid>,
+    ) -> Result<(), DbErr> {
+        permission_user::Entity::delete_many()
+            .filter(permission_user::Column::UserId.eq(self.id))
+            .filter(permission_user::Column::PermissionId.is_in(permissions))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn roles(&self, db: &DatabaseConnection) -> Result<Vec<roles::Model>, DbErr> {
         let query = roles::Entity::find()
             .inner_join(role_user::Entity)
@@ -304,11 +641,60 @@ impl Model {
         &self,
         db: &DatabaseConnection,
         expired_at: Option<NaiveDateTime>,
+    ) -> Result<tokens::Model, DbErr> {
+        self.generate_session(db, expired_at, None, None).await
+    }
+
+    /// Record a successful login, only `last_login_at` is written
+    pub async fn touch_last_login(&self, db: &DatabaseConnection) -> Result<(), DbErr> {
+        Entity::update_many()
+            .col_expr(Column::LastLoginAt, Expr::value(now()))
+            .filter(Column::Id.eq(self.id))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn generate_session(
+        &self,
+        db: &DatabaseConnection,
+        expired_at: Option<NaiveDateTime>,
+        user_agent: Option<String>,
+        ip_address: Option<String>,
     ) -> Result<tokens::Model, DbErr> {
         let token = tokens::Model {
             id: Uuid::new_v4(),
             user_id: self.id,
             expired_at,
+            user_agent,
+            ip_address,
+            created_at: Some(now()),
+            last_used_at: Some(now()),
+            impersonated_by: None,
+            session_id: Uuid::new_v4(),
+        };
+
+        token.store(db).await
+    }
+
+    /// Issue a token acting as this user on behalf of `impersonated_by`
+    pub async fn generate_impersonation(
+        &self,
+        db: &DatabaseConnection,
+        impersonated_by: Uuid,
+        expired_at: NaiveDateTime,
+    ) -> Result<tokens::Model, DbErr> {
+        let token = tokens::Model {
+            id: Uuid::new_v4(),
+            user_id: self.id,
+            expired_at: Some(expired_at),
+            user_agent: None,
+            ip_address: None,
+            created_at: Some(now()),
+            last_used_at: Some(now()),
+            impersonated_by: Some(impersonated_by),
+            session_id: Uuid::new_v4(),
         };
 
         token.store(db).await
@@ -1,5 +1,6 @@
+use lighter_common::prelude::NaiveDateTime;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Clone, Debug, Deserialize, Serialize, ToSchema, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
@@ -9,3 +10,37 @@ pub struct LoginRequest {
     #[schema(example = "password")]
     pub password: String,
 }
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoParams, PartialEq, Eq, Hash)]
+#[into_params(parameter_in = Query)]
+pub struct VerifyEmailRequest {
+    #[schema()]
+    pub token: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyRequest {
+    /// Permission codes the key is allowed to use
+    #[schema(example = json!(["READ_USER"]))]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    #[schema(example = "2021-01-01T00:00:00")]
+    pub expired_at: Option<NaiveDateTime>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, PartialEq, Eq, Hash)]
+pub struct PermissionCheckRequest {
+    /// Permission codes to check for current user
+    #[schema(example = json!(["READ_USER", "UPDATE_ROLE"]))]
+    pub permissions: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, PartialEq, Eq, Hash)]
+pub struct PasswordStrengthRequest {
+    #[schema(example = "correct horse battery staple")]
+    pub password: String,
+    /// Penalize passwords containing it, when known
+    #[schema(example = "john.doe")]
+    pub username: Option<String>,
+}
@@ -1,4 +1,140 @@
+use lighter_common::prelude::*;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+use crate::entities::v1::{permissions, roles, users};
+
 pub mod auth;
 pub mod permission;
 pub mod role;
 pub mod user;
+
+/// Rules for a list of ids sent in a request body
+#[derive(Clone, Copy, Debug)]
+pub struct UuidListRules {
+    /// Singular name of the referenced items, used in messages
+    pub item: &'static str,
+    /// At least one id must be given
+    pub required: bool,
+    /// Most distinct ids accepted
+    pub max: usize,
+    /// Reject repeated ids instead of counting them once
+    pub unique: bool,
+}
+
+impl UuidListRules {
+    /// Check the ids against every rule, returning a message per failed rule
+    pub fn validate(&self, ids: &[Uuid]) -> Vec<String> {
+        let mut errors = vec![];
+        let mut distinct = ids.to_vec();
+
+        distinct.sort();
+        distinct.dedup();
+
+        if self.required && ids.is_empty() {
+            errors.push(format!("At least one {} is required.", self.item));
+        }
+
+        if distinct.len() > self.max {
+            errors.push(format!(
+                "At most {} {}s can be assigned.",
+                self.max, self.item
+            ));
+        }
+
+        if self.unique && distinct.len() != ids.len() {
+            for id in &distinct {
+                if ids.iter().filter(|other| *other == id).count() > 1 {
+                    errors.push(format!("{} {} is repeated.", capitalize(self.item), id));
+                }
+            }
+        }
+
+        errors
+    }
+}
+
+/// Permissions behind the ids of a request body, duplicates count once
+///
+/// Failed list rules and unknown ids are added to `validation` under `permissions`,
+/// a malformed list isn't looked up at all
+pub async fn find_permissions(
+    db: &DatabaseConnection,
+    validation: &mut Validation,
+    ids: &[Uuid],
+    max: usize,
+) -> Result<Vec<permissions::Model>, DbErr> {
+    let errors = assignments("permission", max).validate(ids);
+
+    if !errors.is_empty() {
+        for message in errors {
+            validation.add("permissions", message);
+        }
+
+        return Ok(vec![]);
+    }
+
+    let ids = users::Model::unique_ids(ids);
+    let permissions = permissions::Entity::find()
+        .filter(permissions::Column::Id.is_in(ids.clone()))
+        .all(db)
+        .await?;
+
+    for id in &ids {
+        if !permissions.iter().any(|permission| permission.id == *id) {
+            validation.add("permissions", format!("Permission {} does not exist.", id));
+        }
+    }
+
+    Ok(permissions)
+}
+
+/// Roles behind the ids of a request body, see [`find_permissions`]
+pub async fn find_roles(
+    db: &DatabaseConnection,
+    validation: &mut Validation,
+    ids: &[Uuid],
+    max: usize,
+) -> Result<Vec<roles::Model>, DbErr> {
+    let errors = assignments("role", max).validate(ids);
+
+    if !errors.is_empty() {
+        for message in errors {
+            validation.add("roles", message);
+        }
+
+        return Ok(vec![]);
+    }
+
+    let ids = users::Model::unique_ids(ids);
+    let roles = roles::Entity::find()
+        .filter(roles::Column::Id.is_in(ids.clone()))
+        .all(db)
+        .await?;
+
+    for id in &ids {
+        if !roles.iter().any(|role| role.id == *id) {
+            validation.add("roles", format!("Role {} does not exist.", id));
+        }
+    }
+
+    Ok(roles)
+}
+
+// assignments are stored as a set, so repeated ids aren't an error
+fn assignments(item: &'static str, max: usize) -> UuidListRules {
+    UuidListRules {
+        item,
+        required: false,
+        max,
+        unique: false,
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
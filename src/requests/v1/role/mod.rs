@@ -1,11 +1,34 @@
 use lighter_common::prelude::Uuid;
-use serde::Deserialize;
-use utoipa::ToSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Clone, Deserialize, ToSchema)]
+#[derive(Clone, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
 pub struct RoleRequest {
     #[schema(example = "Manager")]
     pub name: String,
     #[schema()]
     pub permissions: Vec<Uuid>,
+    #[serde(default)]
+    #[schema()]
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RoleInclude {
+    /// Comma separated relations to expand, e.g. `permissions`
+    #[serde(default)]
+    #[param(example = "permissions")]
+    pub include: Option<String>,
+}
+
+impl RoleInclude {
+    pub fn permissions(&self) -> bool {
+        self.include
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .any(|relation| relation.trim() == "permissions")
+    }
 }
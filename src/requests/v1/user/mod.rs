@@ -1,6 +1,6 @@
 use lighter_common::prelude::Uuid;
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Clone, Deserialize, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -50,3 +50,32 @@ pub struct UserUpdatePasswordRequest {
     #[schema(example = "password")]
     pub password_confirmation: String,
 }
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserPaginationFilter {
+    #[serde(default)]
+    #[param(example = false)]
+    pub include_deleted: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UserDeleteFilter {
+    /// Erase an already soft deleted user and everything tied to it
+    #[serde(default)]
+    #[param(example = false)]
+    pub purge: bool,
+}
+
+#[derive(Clone, Deserialize, Serialize, ToSchema)]
+pub struct UserEmailRequest {
+    #[schema(example = "john.doe@example")]
+    pub email: String,
+}
+
+#[derive(Clone, Deserialize, Serialize, ToSchema)]
+pub struct UserPermissionsRequest {
+    #[schema()]
+    pub permissions: Vec<Uuid>,
+}
@@ -0,0 +1,37 @@
+use lighter_common::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    Serialize,
+    ToSchema,
+    IntoResponses,
+    PartialEq,
+    Eq,
+    PaginationRequest,
+    PaginationResponse,
+)]
+#[response(status = 200, description = "OK")]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLog {
+    #[schema()]
+    pub id: Uuid,
+    #[schema()]
+    pub actor_user_id: Option<Uuid>,
+    #[order]
+    #[schema(example = "login")]
+    pub action: String,
+    #[schema(example = "user")]
+    pub target_type: Option<String>,
+    #[schema()]
+    pub target_id: Option<Uuid>,
+    #[schema(example = "127.0.0.1")]
+    pub ip: Option<String>,
+    #[schema(value_type = Option<Object>)]
+    pub metadata: Option<serde_json::Value>,
+    #[schema(example = "2021-01-01T00:00:00")]
+    pub created_at: NaiveDateTime,
+}
@@ -2,8 +2,10 @@ use lighter_common::{base58, prelude::*};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoResponses, ToSchema};
 
+use crate::entities::v1::tokens;
 use crate::middlewares::v1::auth::internal::Auth;
 use crate::responses::v1::user::complete::UserWithPermissionAndRole;
+use crate::responses::v1::user::simple::User;
 
 #[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
 #[response(status = 201, description = "Auhenticated")]
@@ -30,3 +32,188 @@ impl Responder for Authenticated {
         HttpResponse::Created().json(self)
     }
 }
+
+/// New session without the permissions and roles of the user
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, PartialEq, Eq, Hash)]
+pub struct SlimAuthenticated {
+    #[schema()]
+    pub token: String,
+    #[schema()]
+    pub user: User,
+}
+
+impl From<Authenticated> for SlimAuthenticated {
+    fn from(authenticated: Authenticated) -> Self {
+        let user = authenticated.user;
+
+        Self {
+            token: authenticated.token,
+            user: User {
+                id: user.id,
+                name: user.name,
+                email: user.email,
+                email_verified_at: user.email_verified_at,
+                username: user.username,
+            },
+        }
+    }
+}
+
+impl Responder for SlimAuthenticated {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Created().json(self)
+    }
+}
+
+/// Identity of current user with permission and role codes only
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(status = 200, description = "OK")]
+#[serde(rename_all = "camelCase")]
+pub struct Me {
+    #[schema()]
+    pub id: Uuid,
+    #[schema(example = "john.doe")]
+    pub username: String,
+    #[schema(example = json!(["READ_USER"]))]
+    pub permissions: Vec<String>,
+    #[schema(example = json!(["ADMIN"]))]
+    pub roles: Vec<String>,
+    /// User acting through an impersonation token, null otherwise
+    #[schema()]
+    pub impersonated_by: Option<Uuid>,
+}
+
+impl From<Auth> for Me {
+    fn from(auth: Auth) -> Self {
+        Self {
+            id: auth.user.id,
+            username: auth.user.username,
+            permissions: auth
+                .permissions
+                .into_iter()
+                .map(|permission| permission.code)
+                .collect(),
+            roles: auth.roles.into_iter().map(|role| role.code).collect(),
+            impersonated_by: auth.impersonated_by,
+        }
+    }
+}
+
+impl Responder for Me {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok().json(self)
+    }
+}
+
+/// Verification token sent out-of-band, the token itself is never returned
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(status = 202, description = "Verification sent")]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationSent {
+    #[schema(example = "2021-01-01T00:00:00")]
+    pub expired_at: NaiveDateTime,
+}
+
+impl Responder for VerificationSent {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Accepted().json(self)
+    }
+}
+
+/// Session of a user, `id` is a handle for revoking it and never the token itself
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Session {
+    #[schema()]
+    pub id: Uuid,
+    #[schema()]
+    pub user_agent: Option<String>,
+    #[schema()]
+    pub ip_address: Option<String>,
+    #[schema(example = "2021-01-01T00:00:00")]
+    pub created_at: Option<NaiveDateTime>,
+    #[schema(example = "2021-01-01T00:00:00")]
+    pub last_used_at: Option<NaiveDateTime>,
+    #[schema()]
+    pub current: bool,
+}
+
+impl From<tokens::Model> for Session {
+    fn from(token: tokens::Model) -> Self {
+        Self {
+            id: token.session_id,
+            user_agent: token.user_agent,
+            ip_address: token.ip_address,
+            created_at: token.created_at,
+            last_used_at: token.last_used_at,
+            current: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(
+    status = 201,
+    description = "Api key created, the key is only shown once"
+)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKey {
+    #[schema()]
+    pub id: Uuid,
+    #[schema()]
+    pub key: String,
+    #[schema(example = json!(["READ_USER"]))]
+    pub scopes: Vec<String>,
+    #[schema(example = "2021-01-01T00:00:00")]
+    pub expired_at: Option<NaiveDateTime>,
+}
+
+impl Responder for ApiKey {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Created().json(self)
+    }
+}
+
+/// Estimated strength of a password, `score` ranges from 0 (trivial) to 4 (strong)
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(status = 200, description = "OK")]
+pub struct PasswordStrength {
+    #[schema(example = 3)]
+    pub score: u8,
+    #[schema()]
+    pub warnings: Vec<String>,
+    #[schema()]
+    pub suggestions: Vec<String>,
+}
+
+impl Responder for PasswordStrength {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok().json(self)
+    }
+}
+
+/// Number of active sessions of a user
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(status = 200, description = "OK")]
+pub struct SessionCount {
+    #[schema(example = 2)]
+    pub count: u64,
+}
+
+impl Responder for SessionCount {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok().json(self)
+    }
+}
@@ -0,0 +1,39 @@
+use lighter_common::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(status = 200, description = "Authenticated session cache statistics")]
+pub struct CacheStats {
+    /// Cached sessions
+    #[schema(example = 12)]
+    pub size: u64,
+    #[schema(example = 340)]
+    pub hits: u64,
+    #[schema(example = 15)]
+    pub misses: u64,
+}
+
+impl Responder for CacheStats {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok().json(self)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(status = 200, description = "Authenticated session cache cleared")]
+pub struct CacheCleared {
+    /// Cached sessions removed
+    #[schema(example = 12)]
+    pub removed: u64,
+}
+
+impl Responder for CacheCleared {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok().json(self)
+    }
+}
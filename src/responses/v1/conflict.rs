@@ -0,0 +1,30 @@
+use lighter_common::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoResponses, ToSchema};
+
+/// Request clashing with an existing record, `field` names the duplicated value
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(status = 409, description = "Conflict")]
+pub struct Conflict {
+    #[schema(example = "email")]
+    pub field: String,
+    #[schema(example = "Email already exists.")]
+    pub message: String,
+}
+
+impl Conflict {
+    pub fn new<F: ToString, M: ToString>(field: F, message: M) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Responder for Conflict {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Conflict().json(self)
+    }
+}
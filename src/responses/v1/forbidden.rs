@@ -0,0 +1,27 @@
+use lighter_common::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoResponses, ToSchema};
+
+/// Authenticated, but not allowed to act on the target
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(status = 403, description = "Forbidden")]
+pub struct Forbidden {
+    #[schema(example = "Missing REVOKE_TOKEN permission")]
+    pub message: String,
+}
+
+impl Forbidden {
+    pub fn new<M: ToString>(message: M) -> Self {
+        Self {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Responder for Forbidden {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Forbidden().json(self)
+    }
+}
@@ -1,4 +1,9 @@
+pub mod audit;
 pub mod auth;
+pub mod cache;
+pub mod conflict;
+pub mod forbidden;
+pub mod health;
 pub mod permission;
 pub mod role;
 pub mod user;
@@ -2,6 +2,8 @@ use lighter_common::prelude::*;
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoResponses, ToSchema};
 
+use crate::responses::v1::permission::Permission;
+
 #[derive(
     Clone,
     Debug,
@@ -16,6 +18,7 @@ use utoipa::{IntoResponses, ToSchema};
     PaginationResponse,
 )]
 #[response(status = 200, description = "OK")]
+#[serde(rename_all = "camelCase")]
 pub struct Role {
     #[schema()]
     pub id: Uuid,
@@ -25,4 +28,53 @@ pub struct Role {
     #[order]
     #[schema(example = "Manager")]
     pub name: String,
+    #[schema()]
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(status = 200, description = "OK")]
+#[serde(rename_all = "camelCase")]
+pub struct RoleWithPermissions {
+    #[schema()]
+    pub id: Uuid,
+    #[schema(example = "MANAGER")]
+    pub code: String,
+    #[schema(example = "Manager")]
+    pub name: String,
+    #[schema()]
+    pub parent_id: Option<Uuid>,
+    #[schema()]
+    pub permissions: Vec<Permission>,
+}
+
+impl<R, P> From<(R, Vec<P>)> for RoleWithPermissions
+where
+    R: Into<Role>,
+    P: Into<Permission>,
+{
+    fn from((role, permissions): (R, Vec<P>)) -> Self {
+        let role: Role = role.into();
+
+        Self {
+            id: role.id,
+            code: role.code,
+            name: role.name,
+            parent_id: role.parent_id,
+            permissions: permissions.into_iter().map(|p| p.into()).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq, Eq, Hash)]
+#[response(status = 200, description = "OK")]
+pub struct RoleWithPermissionsPaginationResponse {
+    #[schema()]
+    pub total: u64,
+    #[schema()]
+    pub page: u64,
+    #[schema()]
+    pub pages: u64,
+    #[schema()]
+    pub data: Vec<RoleWithPermissions>,
 }
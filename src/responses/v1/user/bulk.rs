@@ -0,0 +1,40 @@
+use lighter_common::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::{IntoResponses, ToSchema};
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUserResult {
+    /// Position of the user in the request
+    #[schema(example = 0)]
+    pub index: usize,
+    #[schema(example = true)]
+    pub success: bool,
+    /// Status the single create endpoint would have answered with
+    #[schema(example = 200)]
+    pub status: u16,
+    /// The created user, or the errors that rejected it
+    #[schema(value_type = Object)]
+    pub body: Value,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq)]
+#[response(status = 200, description = "Result of every user in the batch")]
+#[serde(rename_all = "camelCase")]
+pub struct BulkUserResponse {
+    #[schema(example = 9)]
+    pub created: u64,
+    #[schema(example = 1)]
+    pub failed: u64,
+    #[schema()]
+    pub results: Vec<BulkUserResult>,
+}
+
+impl Responder for BulkUserResponse {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok().json(self)
+    }
+}
@@ -0,0 +1,45 @@
+use lighter_common::prelude::*;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoResponses, ToSchema};
+
+use crate::responses::v1::audit::AuditLog;
+use crate::responses::v1::auth::Session;
+use crate::responses::v1::permission::Permission;
+use crate::responses::v1::role::Role;
+
+use super::simple::User;
+
+/// Everything stored about a user, password hashes excluded
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema, IntoResponses, PartialEq)]
+#[response(status = 200, description = "OK")]
+#[serde(rename_all = "camelCase")]
+pub struct UserExport {
+    #[schema()]
+    pub profile: User,
+    #[schema(example = "2021-01-01T00:00:00")]
+    pub created_at: NaiveDateTime,
+    #[schema(example = "2021-01-01T00:00:00")]
+    pub last_login_at: Option<NaiveDateTime>,
+    /// Roles assigned directly
+    #[schema()]
+    pub roles: Vec<Role>,
+    /// Permissions granted directly or through a role
+    #[schema()]
+    pub permissions: Vec<Permission>,
+    /// Sessions that haven't expired
+    #[schema()]
+    pub sessions: Vec<Session>,
+    /// Audit entries the user performed or was the target of, oldest first
+    #[schema()]
+    pub audit_logs: Vec<AuditLog>,
+    #[schema(example = "2021-01-01T00:00:00")]
+    pub exported_at: NaiveDateTime,
+}
+
+impl Responder for UserExport {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse<Self::Body> {
+        HttpResponse::Ok().json(self)
+    }
+}
@@ -1,2 +1,4 @@
+pub mod bulk;
 pub mod complete;
+pub mod export;
 pub mod simple;
@@ -1,21 +1,97 @@
+use std::sync::OnceLock;
+
 use lighter_common::prelude::*;
 use utoipa::OpenApi;
-use utoipa_swagger_ui::{SwaggerUi, Url};
+use utoipa_swagger_ui::SwaggerUi;
 
 use crate::api::Definition;
+use crate::config::AuthConfig;
 use crate::controllers;
-use crate::middlewares::v1::auth::Authenticated;
+use crate::middlewares::v1::auth::{Authenticated, EmailVerification};
+use crate::middlewares::v1::idempotency::Idempotency;
+use crate::middlewares::v1::request_id::RequestIdMiddleware;
+use crate::middlewares::v1::version::ApiVersionMiddleware;
+use crate::services::v1::notification::Notifications;
+
+/// Stores every worker has to see the same copy of
+///
+/// `route` runs once per worker, a store created there would only know the
+/// requests served by that worker
+#[derive(Clone)]
+pub struct Shared {
+    pub authenticated: Data<Authenticated>,
+    pub verification: Data<EmailVerification>,
+    pub idempotency: Data<Idempotency>,
+}
+
+impl Shared {
+    pub fn new() -> Self {
+        Self {
+            authenticated: Data::new(Authenticated::new()),
+            verification: Data::new(EmailVerification::new()),
+            idempotency: Data::new(Idempotency::new()),
+        }
+    }
+
+    /// Instance of the running server, created by the first worker
+    pub fn global() -> &'static Self {
+        static SHARED: OnceLock<Shared> = OnceLock::new();
+
+        SHARED.get_or_init(Self::new)
+    }
+}
+
+impl Default for Shared {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 pub fn route(app: &mut ServiceConfig) {
-    app.app_data(Data::new(Authenticated::new()));
+    configure(app, Shared::global());
+}
+
+pub fn configure(app: &mut ServiceConfig, shared: &Shared) {
+    app.app_data(Data::new(AuthConfig::env()));
+    app.app_data(shared.authenticated.clone());
+    app.app_data(shared.verification.clone());
+    app.app_data(shared.idempotency.clone());
+    app.app_data(Data::new(Notifications::default()));
+    // `ServiceConfig` can't be wrapped, so every route lives in an unprefixed scope
+    app.service(
+        web::scope("")
+            .wrap(RequestIdMiddleware)
+            .wrap(versioning())
+            .configure(services),
+    );
+}
+
+/// Deprecated endpoints, announced through the `Deprecation` and `Sunset` headers
+///
+/// e.g. `.deprecate(Method::GET, "/user", Deprecation { since, sunset: Some(date) })`
+fn versioning() -> ApiVersionMiddleware {
+    ApiVersionMiddleware::new()
+}
+
+fn services(app: &mut ServiceConfig) {
     app.service(index);
+    // Health
+    app.service(controllers::v1::health::live);
+    app.service(controllers::v1::health::ready);
     // User
     app.service(controllers::v1::user::paginate);
     app.service(controllers::v1::user::store);
+    app.service(controllers::v1::user::bulk);
     app.service(controllers::v1::user::show);
+    app.service(controllers::v1::user::export);
+    app.service(controllers::v1::user::session_count);
     app.service(controllers::v1::user::update_general_information);
     app.service(controllers::v1::user::update_password);
+    app.service(controllers::v1::user::change_email);
     app.service(controllers::v1::user::delete);
+    app.service(controllers::v1::user::restore);
+    app.service(controllers::v1::user::assign_permissions);
+    app.service(controllers::v1::user::revoke_permissions);
     // Permission
     app.service(controllers::v1::permission::paginate);
     app.service(controllers::v1::permission::store);
@@ -31,14 +107,32 @@ pub fn route(app: &mut ServiceConfig) {
     // Auth
     app.service(controllers::v1::auth::login);
     app.service(controllers::v1::auth::authenticated);
+    app.service(controllers::v1::auth::me);
+    app.service(controllers::v1::auth::can);
     app.service(controllers::v1::auth::logout);
+    app.service(controllers::v1::auth::logout_others);
+    app.service(controllers::v1::auth::sessions);
+    app.service(controllers::v1::auth::revoke_session);
+    app.service(controllers::v1::auth::revoke_token);
+    app.service(controllers::v1::auth::create_api_key);
+    app.service(controllers::v1::auth::revoke_api_key);
+    app.service(controllers::v1::auth::send_verification);
+    app.service(controllers::v1::auth::verify_email);
+    app.service(controllers::v1::auth::password_strength);
+    // Audit
+    app.service(controllers::v1::audit::paginate);
+    // Cache
+    app.service(controllers::v1::cache::stats);
+    app.service(controllers::v1::cache::clear);
+    // Impersonation
+    app.service(controllers::v1::impersonation::impersonate);
 
     // must at the end!
-    app.service(web::redirect("/doc", "/doc/"));
-    app.service(SwaggerUi::new("/doc/{_:.*}").urls(vec![(
-        Url::new("Authentication", "/api.json"),
-        Definition::openapi(),
-    )]));
+    app.service(web::redirect("/docs", "/docs/"));
+    // the old locations of the ui and the spec
+    app.service(web::redirect("/doc", "/docs/"));
+    app.service(web::redirect("/api.json", "/openapi.json"));
+    app.service(SwaggerUi::new("/docs/{_:.*}").url("/openapi.json", Definition::openapi()));
 }
 
 #[get("/")]
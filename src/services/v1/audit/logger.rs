@@ -0,0 +1,114 @@
+use std::future::{ready, Ready};
+
+use actix_web::dev::Payload;
+use actix_web::FromRequest;
+use lighter_common::prelude::*;
+
+use crate::entities::v1::audit_logs::Model;
+use crate::middlewares::v1::request_id::RequestId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Login,
+    LoginFailed,
+    Logout,
+    LogoutOthers,
+    RevokeSession,
+    RevokeToken,
+    UpdatePassword,
+    PurgeUser,
+    AssignPermissions,
+    RevokePermissions,
+    ClearCache,
+    Impersonate,
+}
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Login => "login",
+            Self::LoginFailed => "login_failed",
+            Self::Logout => "logout",
+            Self::LogoutOthers => "logout_others",
+            Self::RevokeSession => "revoke_session",
+            Self::RevokeToken => "revoke_token",
+            Self::UpdatePassword => "update_password",
+            Self::PurgeUser => "purge_user",
+            Self::AssignPermissions => "assign_permissions",
+            Self::RevokePermissions => "revoke_permissions",
+            Self::ClearCache => "clear_cache",
+            Self::Impersonate => "impersonate",
+        }
+    }
+}
+
+/// Append-only trail of auth-sensitive operations
+///
+/// Writes are best-effort, a failure is logged and never fails the request
+#[derive(Clone)]
+pub struct AuditLogger {
+    db: Data<DatabaseConnection>,
+    ip: Option<String>,
+    request_id: String,
+}
+
+impl AuditLogger {
+    pub async fn log(
+        &self,
+        actor_user_id: Option<Uuid>,
+        action: Action,
+        target: Option<(&str, Uuid)>,
+        mut metadata: Option<serde_json::Value>,
+    ) {
+        if !self.request_id.is_empty() {
+            let request_id = serde_json::Value::String(self.request_id.clone());
+
+            if let serde_json::Value::Object(map) =
+                metadata.get_or_insert_with(|| serde_json::json!({}))
+            {
+                map.insert("requestId".to_string(), request_id);
+            }
+        }
+
+        let log = Model {
+            id: Uuid::new_v4(),
+            actor_user_id,
+            action: action.as_str().to_string(),
+            target_type: target.map(|(target_type, _)| target_type.to_string()),
+            target_id: target.map(|(_, target_id)| target_id),
+            ip: self.ip.clone(),
+            metadata,
+            created_at: now(),
+        };
+
+        if let Err(e) = log.store(&self.db).await {
+            tracing::error!("Failed to write audit log");
+            tracing::error!("Error: {}", e);
+        }
+    }
+}
+
+impl FromRequest for AuditLogger {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let db = match req.app_data::<Data<DatabaseConnection>>().cloned() {
+            Some(db) => db,
+            None => {
+                tracing::error!("Failed to get database connection");
+
+                return ready(Err(InternalServerError::new(
+                    "Failed to get database connection",
+                )
+                .into()));
+            }
+        };
+
+        ready(Ok(Self {
+            db,
+            ip: req.peer_addr().map(|addr| addr.ip().to_string()),
+            request_id: RequestId::of(req),
+        }))
+    }
+}
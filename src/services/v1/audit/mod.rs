@@ -0,0 +1,4 @@
+pub mod logger;
+pub mod paginate;
+
+pub use logger::{Action, AuditLogger};
@@ -0,0 +1,53 @@
+use lighter_common::prelude::*;
+use sea_orm::prelude::*;
+use sea_orm::{QueryOrder, QuerySelect};
+
+use crate::entities::v1::audit_logs::{Column, Entity};
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::responses::v1::audit::{
+    AuditLogPaginationOrder, AuditLogPaginationRequest, AuditLogPaginationResponse,
+};
+
+pub async fn paginate(
+    db: &DatabaseConnection,
+    auth: Auth,
+    request: AuditLogPaginationRequest,
+) -> Result<AuditLogPaginationResponse, Error> {
+    if !auth.has_permission("READ_AUDIT") {
+        return Err(Unauthorized::new("Missing READ_AUDIT permission").into());
+    }
+
+    let mut query = Entity::find();
+
+    if let Some(search) = request.search() {
+        let search = format!("%{}%", search);
+
+        query = query.filter(
+            Condition::any()
+                .add(Column::Action.like(search.clone()))
+                .add(Column::TargetType.like(search.clone())),
+        );
+    }
+
+    let total = query.clone().count(db).await?;
+
+    query = query
+        .limit(request.limit())
+        .offset(request.offset())
+        .order_by(
+            match request.order() {
+                AuditLogPaginationOrder::Action => Column::Action,
+                AuditLogPaginationOrder::CreatedAt => Column::CreatedAt,
+            },
+            request.sort().into(),
+        );
+
+    let logs = query.all(db).await?;
+
+    Ok(AuditLogPaginationResponse {
+        total,
+        page: request.page(),
+        pages: crate::services::v1::pages(total, request.limit()),
+        data: logs.into_iter().map(|log| log.into()).collect(),
+    })
+}
@@ -0,0 +1,23 @@
+use std::collections::BTreeMap;
+
+use lighter_common::prelude::*;
+
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::requests::v1::auth::PermissionCheckRequest;
+
+pub async fn can(
+    auth: Auth,
+    request: PermissionCheckRequest,
+) -> Result<Json<BTreeMap<String, bool>>, Error> {
+    let checked = request
+        .permissions
+        .into_iter()
+        .map(|code| {
+            let allowed = auth.has_permission(&code);
+
+            (code, allowed)
+        })
+        .collect();
+
+    Ok(Json(checked))
+}
@@ -0,0 +1,52 @@
+use lighter_common::prelude::*;
+
+use crate::entities::v1::api_keys::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::requests::v1::auth::ApiKeyRequest;
+use crate::responses::v1::auth::ApiKey;
+
+pub async fn create_api_key(
+    auth: Auth,
+    db: &DatabaseConnection,
+    request: ApiKeyRequest,
+) -> Result<ApiKey, Error> {
+    let mut validation = Validation::new();
+    let mut scopes = request
+        .scopes
+        .iter()
+        .map(|scope| scope.trim().to_uppercase())
+        .collect::<Vec<_>>();
+
+    scopes.sort();
+    scopes.dedup();
+
+    if scopes.is_empty() {
+        validation.add("scopes", "Scopes are required");
+    }
+
+    // a key can never do more than its owner
+    for scope in &scopes {
+        if !auth.has_permission(scope) {
+            validation.add("scopes", format!("Scope {} is not granted.", scope));
+        }
+    }
+
+    if let Some(expired_at) = request.expired_at {
+        if expired_at <= now() {
+            validation.add("expiredAt", "Expiration must be in the future");
+        }
+    }
+
+    if !validation.is_empty() {
+        return Err(validation.into());
+    }
+
+    let (model, key) = Model::generate(db, auth.user.id, scopes, request.expired_at).await?;
+
+    Ok(ApiKey {
+        id: model.id,
+        key,
+        scopes: model.scopes(),
+        expired_at: model.expired_at,
+    })
+}
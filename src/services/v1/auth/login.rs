@@ -1,7 +1,9 @@
+use std::sync::OnceLock;
 use std::time::Duration;
 
 use lighter_common::prelude::*;
 
+use crate::config::AuthConfig;
 use crate::entities::v1::users::Model;
 use crate::middlewares::v1::auth::internal::Auth;
 use crate::middlewares::v1::auth::Authenticated as Cache;
@@ -11,52 +13,85 @@ use crate::responses::v1::auth::Authenticated;
 // 1 hour
 pub const LIFETIME: Duration = Duration::from_secs(60 * 60);
 
+/// Verified against when the account doesn't exist, so both failures cost a hash
+fn decoy() -> &'static String {
+    static DECOY: OnceLock<String> = OnceLock::new();
+
+    DECOY.get_or_init(|| Hash::make(Uuid::nil(), "decoy password").to_string())
+}
+
 pub async fn login(
     db: &DatabaseConnection,
     cached: &Cache,
+    config: &AuthConfig,
     request: LoginRequest,
+    user_agent: Option<String>,
+    ip_address: Option<String>,
 ) -> Result<Authenticated, Error> {
     let mut validation = Validation::new();
-    let email_or_username = request.email_or_username.trim().to_lowercase();
+    let is_email = request.email_or_username.contains('@');
+    let email_or_username = match is_email {
+        true => Model::normalize_email(&request.email_or_username, config.canonicalize_gmail),
+        false => Model::normalize_username(&request.email_or_username),
+    };
     let password = request.password;
+    let identifier = config.login_identifier;
 
     if email_or_username.is_empty() {
-        validation.add("email_or_username", "Email or username field is required");
-    } else {
-        if !Model::email_or_username_exists(db, &email_or_username).await {
-            validation.add("email_or_username", "Email or username not found");
-        }
+        validation.add("emailOrUsername", "Email or username field is required");
+    } else if is_email && !identifier.allows_email() {
+        validation.add("emailOrUsername", "Login with your username");
+    } else if !is_email && !identifier.allows_username() {
+        validation.add("emailOrUsername", "Login with your email");
     }
 
+    // length rules belong to the password policy at creation, checking them here
+    // would lock out accounts created under a shorter minimum
     if password.is_empty() {
         validation.add("password", "Password field is required");
-    } else {
-        if password.len() < 8 {
-            validation.add("password", "Password must be at least 8 characters");
-        }
     }
 
     if !validation.is_empty() {
         return Err(validation.into());
     }
 
-    let user = Model::find_by_email_or_username(db, &email_or_username)
-        .await
-        .unwrap();
+    let user = match is_email {
+        true => Model::find_by_email(db, &email_or_username).await,
+        false => Model::find_by_username(db, &email_or_username).await,
+    };
 
-    if !Hash::from(&user.password).verify(user.id, &password) {
-        validation.add("password", "Password is incorrect");
+    // same answer, and comparable timing, for an unknown account and a wrong password
+    let user = match user {
+        Some(user) if Hash::from(&user.password).verify(user.id, &password) => user,
+        Some(_) => return Err(Unauthorized::new("Invalid credentials").into()),
+        None => {
+            let _ = Hash::from(decoy()).verify(Uuid::nil(), &password);
+
+            return Err(Unauthorized::new("Invalid credentials").into());
+        }
+    };
+
+    if config.require_verified_email && user.email_verified_at.is_none() {
+        return Err(Unauthorized::new("Email is not verified").into());
     }
 
-    if !validation.is_empty() {
-        return Err(validation.into());
+    let token = user
+        .generate_session(db, config.token_expiry(None), user_agent, ip_address)
+        .await?;
+    // best-effort, losing the timestamp isn't worth failing the login over
+    if let Err(e) = user.touch_last_login(db).await {
+        tracing::error!("Failed to record last login of user {}", user.id);
+        tracing::error!("Error: {}", e);
     }
 
-    let token = user.generate_token(db, None).await?;
     let permissions = user.permissions(db).await?;
     let roles = user.roles(db).await?;
     let auth = Auth {
         id: token.id,
+        last_login_at: Some(now()),
+        expired_at: token.expired_at,
+        last_used_at: token.last_used_at,
+        impersonated_by: None,
         user: user.into(),
         permissions: permissions
             .into_iter()
@@ -4,9 +4,18 @@ use crate::entities::v1::tokens::Model;
 use crate::middlewares::v1::auth::internal::Auth;
 use crate::middlewares::v1::auth::Authenticated as Cache;
 
+/// Destroy every session of the user, an impersonation token only destroys itself
+/// so the impersonated user stays logged in
 pub async fn logout(auth: Auth, db: &DatabaseConnection, cached: &Cache) -> Result<Success, Error> {
+    if auth.impersonated_by.is_some() {
+        Model::logout_session(db, auth.id).await?;
+        cached.remove(auth.id).await;
+
+        return Ok(Success);
+    }
+
     Model::logout(db, auth.user.id).await?;
-    cached.remove(auth.id).await;
+    cached.remove_by_user(auth.user.id, None).await;
 
     Ok(Success)
 }
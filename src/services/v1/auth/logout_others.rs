@@ -0,0 +1,16 @@
+use lighter_common::prelude::*;
+
+use crate::entities::v1::tokens::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+
+pub async fn logout_others(
+    auth: Auth,
+    db: &DatabaseConnection,
+    cached: &Cache,
+) -> Result<Success, Error> {
+    Model::logout_others(db, auth.user.id, auth.id).await?;
+    cached.remove_by_user(auth.user.id, Some(auth.id)).await;
+
+    Ok(Success)
+}
@@ -0,0 +1,6 @@
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::responses::v1::auth::Me;
+
+pub async fn me(auth: Auth) -> Me {
+    auth.into()
+}
@@ -1,3 +1,14 @@
 pub mod authenticated;
+pub mod can;
+pub mod create_api_key;
 pub mod login;
 pub mod logout;
+pub mod logout_others;
+pub mod me;
+pub mod password_strength;
+pub mod revoke_api_key;
+pub mod revoke_session;
+pub mod revoke_token;
+pub mod send_verification;
+pub mod sessions;
+pub mod verify_email;
@@ -0,0 +1,139 @@
+use crate::requests::v1::auth::PasswordStrengthRequest;
+use crate::responses::v1::auth::PasswordStrength;
+
+/// Passwords guessed first by every cracking list, compared case-insensitively
+/// after trailing digits and symbols are dropped
+const COMMON: &[&str] = &[
+    "password",
+    "passw0rd",
+    "qwerty",
+    "qwertyuiop",
+    "asdfgh",
+    "zxcvbn",
+    "letmein",
+    "welcome",
+    "admin",
+    "administrator",
+    "login",
+    "master",
+    "monkey",
+    "dragon",
+    "football",
+    "baseball",
+    "iloveyou",
+    "sunshine",
+    "princess",
+    "shadow",
+    "superman",
+    "trustno",
+    "abc",
+    "secret",
+    "starwars",
+    "whatever",
+    "changeme",
+    "default",
+    "guest",
+    "root",
+    "test",
+    "user",
+];
+
+pub async fn password_strength(request: PasswordStrengthRequest) -> PasswordStrength {
+    strength(
+        &request.password,
+        request.username.as_deref().unwrap_or_default(),
+    )
+}
+
+/// Estimate how hard the password is to guess, scored from 0 (trivial) to 4 (strong)
+///
+/// The estimate is the entropy of the characters that aren't repeats or
+/// continuations of a sequence, over the character classes in use
+pub fn strength(password: &str, username: &str) -> PasswordStrength {
+    let mut warnings = vec![];
+    let mut suggestions = vec![];
+    let chars = password.chars().collect::<Vec<_>>();
+    let lowercase = password.to_lowercase();
+    let username = username.trim().to_lowercase();
+
+    let mut classes = 0;
+    let mut charset = 0;
+
+    for (present, size) in [
+        (chars.iter().any(|c| c.is_lowercase()), 26),
+        (chars.iter().any(|c| c.is_uppercase()), 26),
+        (chars.iter().any(|c| c.is_ascii_digit()), 10),
+        (chars.iter().any(|c| !c.is_alphanumeric()), 33),
+    ] {
+        if present {
+            classes += 1;
+            charset += size;
+        }
+    }
+
+    let mut effective = 0;
+    let mut repeated = false;
+    let mut sequential = false;
+
+    for (i, c) in chars.iter().enumerate() {
+        let previous = match i {
+            0 => None,
+            _ => Some(chars[i - 1]),
+        };
+
+        match previous {
+            Some(previous) if previous == *c => repeated = true,
+            Some(previous) if (previous as u32).abs_diff(*c as u32) == 1 => sequential = true,
+            _ => effective += 1,
+        }
+    }
+
+    let bits = effective as f64 * (charset.max(1) as f64).log2();
+    let mut score = match bits {
+        bits if bits < 28.0 => 0,
+        bits if bits < 36.0 => 1,
+        bits if bits < 60.0 => 2,
+        bits if bits < 80.0 => 3,
+        _ => 4,
+    };
+
+    let stem = lowercase.trim_end_matches(|c: char| !c.is_alphabetic());
+
+    if COMMON.contains(&stem) {
+        score = 0;
+        warnings.push("This is a commonly used password.".to_string());
+        suggestions.push("Avoid common passwords and their variations.".to_string());
+    }
+
+    if !username.is_empty() && lowercase.contains(&username) {
+        score = score.min(1);
+        warnings.push("Passwords containing the username are easy to guess.".to_string());
+        suggestions.push("Don't include your username.".to_string());
+    }
+
+    if repeated {
+        warnings.push("Repeated characters like \"aaa\" are easy to guess.".to_string());
+    }
+
+    if sequential {
+        warnings.push("Sequences like \"abc\" or \"123\" are easy to guess.".to_string());
+    }
+
+    if repeated || sequential {
+        suggestions.push("Avoid repeated characters and sequences.".to_string());
+    }
+
+    if chars.len() < 12 {
+        suggestions.push("Use a longer password, a few unrelated words work well.".to_string());
+    }
+
+    if classes < 3 {
+        suggestions.push("Mix uppercase and lowercase letters, digits and symbols.".to_string());
+    }
+
+    PasswordStrength {
+        score,
+        warnings,
+        suggestions,
+    }
+}
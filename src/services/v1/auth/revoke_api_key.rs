@@ -0,0 +1,20 @@
+use lighter_common::prelude::*;
+use sea_orm::EntityTrait;
+
+use crate::entities::v1::api_keys::{Entity, Model};
+use crate::middlewares::v1::auth::internal::Auth;
+
+pub async fn revoke_api_key(
+    auth: Auth,
+    db: &DatabaseConnection,
+    id: Uuid,
+) -> Result<Success, Error> {
+    let key: Model = match Entity::find_by_id(id).one(db).await? {
+        Some(key) if key.user_id == auth.user.id => key,
+        _ => return Err(NotFound::new("Api key not found.").into()),
+    };
+
+    key.delete(db).await?;
+
+    Ok(Success)
+}
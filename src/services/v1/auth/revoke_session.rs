@@ -0,0 +1,27 @@
+use lighter_common::prelude::*;
+
+use crate::entities::v1::tokens::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+
+pub async fn revoke_session(
+    auth: Auth,
+    db: &DatabaseConnection,
+    cached: &Cache,
+    id: String,
+) -> Result<Success, Error> {
+    let id = match Uuid::parse_str(id.trim()) {
+        Ok(id) => id,
+        Err(_) => return Err(NotFound::new("Session not found.").into()),
+    };
+
+    let token = match Model::find_by_session(db, id).await? {
+        Some(token) if token.user_id == auth.user.id => token,
+        _ => return Err(NotFound::new("Session not found.").into()),
+    };
+
+    token.delete(db).await?;
+    cached.remove(token.id).await;
+
+    Ok(Success)
+}
@@ -0,0 +1,37 @@
+use actix_web::Either;
+use lighter_common::prelude::*;
+
+use crate::entities::v1::tokens::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+use crate::responses::v1::forbidden::Forbidden;
+
+/// Revoke a token by its session handle, of the current user or of anyone with
+/// REVOKE_TOKEN permission, returning the owner of the revoked token
+pub async fn revoke_token(
+    auth: Auth,
+    db: &DatabaseConnection,
+    cached: &Cache,
+    id: String,
+) -> Result<Either<Uuid, Forbidden>, Error> {
+    let id = match Uuid::parse_str(id.trim()) {
+        Ok(id) => id,
+        Err(_) => return Err(NotFound::new("Token not found.").into()),
+    };
+
+    let token = match Model::find_by_session(db, id).await? {
+        Some(token) => token,
+        None => return Err(NotFound::new("Token not found.").into()),
+    };
+
+    if token.user_id != auth.user.id && !auth.has_permission("REVOKE_TOKEN") {
+        return Ok(Either::Right(Forbidden::new(
+            "Missing REVOKE_TOKEN permission",
+        )));
+    }
+
+    token.delete(db).await?;
+    cached.remove(token.id).await;
+
+    Ok(Either::Left(token.user_id))
+}
@@ -0,0 +1,30 @@
+use lighter_common::{base58, prelude::*};
+
+use crate::config::AuthConfig;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::EmailVerification;
+use crate::responses::v1::auth::VerificationSent;
+use crate::services::v1::notification::{Notification, Notifications};
+
+pub async fn send_verification(
+    auth: Auth,
+    config: &AuthConfig,
+    verification: &EmailVerification,
+    notifications: &Notifications,
+) -> VerificationSent {
+    let (token, expired_at) = verification
+        .issue(auth.user.id, config.email_verification_lifetime)
+        .await;
+
+    tracing::info!("Email verification token issued for user {}", auth.user.id);
+
+    // only the owner of the address may see the token
+    notifications.send(Notification::VerifyEmail {
+        user_id: auth.user.id,
+        email: auth.user.email,
+        token: base58::to_string(token),
+        expired_at,
+    });
+
+    VerificationSent { expired_at }
+}
@@ -0,0 +1,22 @@
+use lighter_common::prelude::*;
+
+use crate::entities::v1::tokens::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::responses::v1::auth::Session;
+
+pub async fn sessions(auth: Auth, db: &DatabaseConnection) -> Result<Json<Vec<Session>>, Error> {
+    let sessions = Model::active(db, auth.user.id)
+        .await?
+        .into_iter()
+        .map(|token| {
+            let current = token.id == auth.id;
+
+            Session {
+                current,
+                ..token.into()
+            }
+        })
+        .collect();
+
+    Ok(Json(sessions))
+}
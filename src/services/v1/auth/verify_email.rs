@@ -0,0 +1,71 @@
+use lighter_common::{base58, prelude::*};
+
+use crate::entities::v1::users::Model;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+use crate::middlewares::v1::auth::EmailVerification;
+use crate::requests::v1::auth::VerifyEmailRequest;
+
+pub async fn verify_email(
+    db: &DatabaseConnection,
+    cached: &Cache,
+    verification: &EmailVerification,
+    request: VerifyEmailRequest,
+) -> Result<Success, Error> {
+    let token = match base58::decode(request.token.trim()) {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to decode verification token");
+            tracing::error!("Error: {}", e);
+
+            return Err(BadRequest::new("Invalid verification token").into());
+        }
+    };
+
+    let token = match Uuid::from_slice(&token) {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to convert verification token to uuid");
+            tracing::error!("Error: {}", e);
+
+            return Err(BadRequest::new("Invalid verification token").into());
+        }
+    };
+
+    let (user_id, email, expired_at) = match verification.get(token).await {
+        Some(entry) => entry,
+        None => return Err(BadRequest::new("Verification token not found").into()),
+    };
+
+    if expired_at <= now() {
+        verification.remove(token).await;
+
+        return Err(BadRequest::new("Verification token expired").into());
+    }
+
+    let user = match Model::find_by_id(db, user_id).await {
+        Some(user) => user,
+        None => return Err(NotFound::new("User not found.").into()),
+    };
+
+    match email {
+        Some(email) => {
+            // taken by another account while the change was pending
+            if Model::email_exists(db, &email).await {
+                verification.remove(token).await;
+
+                return Err(BadRequest::new("Email already exists.").into());
+            }
+
+            user.change_email(db, email).await?;
+            cached.remove_by_user(user.id, None).await;
+        }
+        None => {
+            user.mark_email_verified(db).await?;
+            cached.remove_by_user(user.id, None).await;
+        }
+    }
+
+    verification.remove(token).await;
+
+    Ok(Success)
+}
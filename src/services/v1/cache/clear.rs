@@ -0,0 +1,21 @@
+use actix_web::Either;
+use lighter_common::prelude::*;
+
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+use crate::responses::v1::cache::CacheCleared;
+use crate::responses::v1::forbidden::Forbidden;
+
+pub async fn clear(auth: Auth, cached: &Cache) -> Result<Either<CacheCleared, Forbidden>, Error> {
+    if !auth.has_permission("MANAGE_CACHE") {
+        return Ok(Either::Right(Forbidden::new(
+            "Missing MANAGE_CACHE permission",
+        )));
+    }
+
+    let removed = cached.clear().await;
+
+    tracing::info!("Cleared {} cached sessions", removed);
+
+    Ok(Either::Left(CacheCleared { removed }))
+}
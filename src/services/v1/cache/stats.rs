@@ -0,0 +1,17 @@
+use actix_web::Either;
+use lighter_common::prelude::*;
+
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+use crate::responses::v1::cache::CacheStats;
+use crate::responses::v1::forbidden::Forbidden;
+
+pub async fn stats(auth: Auth, cached: &Cache) -> Result<Either<CacheStats, Forbidden>, Error> {
+    if !auth.has_permission("MANAGE_CACHE") {
+        return Ok(Either::Right(Forbidden::new(
+            "Missing MANAGE_CACHE permission",
+        )));
+    }
+
+    Ok(Either::Left(cached.stats()))
+}
@@ -0,0 +1,8 @@
+use crate::responses::v1::health::Health;
+
+pub async fn live() -> Health {
+    Health {
+        status: "ok".to_string(),
+        database: None,
+    }
+}
@@ -0,0 +1,21 @@
+use lighter_common::prelude::*;
+
+use crate::responses::v1::health::Health;
+
+pub async fn ready(db: &DatabaseConnection) -> Health {
+    match db.ping().await {
+        Ok(()) => Health {
+            status: "ok".to_string(),
+            database: Some("ok".to_string()),
+        },
+        Err(e) => {
+            tracing::error!("Database is not reachable");
+            tracing::error!("Error: {}", e);
+
+            Health {
+                status: "unavailable".to_string(),
+                database: Some("unreachable".to_string()),
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use actix_web::Either;
+use lighter_common::prelude::*;
+
+use crate::config::AuthConfig;
+use crate::entities::v1::users::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::responses::v1::auth::Authenticated;
+use crate::responses::v1::forbidden::Forbidden;
+
+pub async fn impersonate(
+    db: &DatabaseConnection,
+    config: &AuthConfig,
+    auth: Auth,
+    id: Uuid,
+) -> Result<Either<Authenticated, Forbidden>, Error> {
+    if !auth.has_permission("IMPERSONATE") {
+        return Ok(Either::Right(Forbidden::new(
+            "Missing IMPERSONATE permission",
+        )));
+    }
+
+    // no chains, an impersonated session can't hop to yet another user
+    if auth.impersonated_by.is_some() {
+        return Ok(Either::Right(Forbidden::new(
+            "Impersonation tokens can't impersonate",
+        )));
+    }
+
+    if auth.user.id == id {
+        return Err(BadRequest::new("Can't impersonate yourself").into());
+    }
+
+    let user = match Model::find_by_id(db, id).await {
+        None => return Err(NotFound::new("User not found.").into()),
+        Some(user) => user,
+    };
+
+    let permissions = user.permissions(db).await?;
+
+    if permissions
+        .iter()
+        .any(|permission| config.impersonation_protected.contains(&permission.code))
+    {
+        return Ok(Either::Right(Forbidden::new("User can't be impersonated")));
+    }
+
+    let roles = user.roles(db).await?;
+    let token = user
+        .generate_impersonation(db, auth.user.id, now() + config.impersonation_ttl)
+        .await?;
+
+    tracing::info!("User {} impersonates user {}", auth.user.id, user.id);
+
+    Ok(Either::Left(
+        Auth {
+            id: token.id,
+            last_login_at: user.last_login_at,
+            expired_at: token.expired_at,
+            last_used_at: token.last_used_at,
+            impersonated_by: token.impersonated_by,
+            user: user.into(),
+            permissions: permissions
+                .into_iter()
+                .map(|permission| permission.into())
+                .collect(),
+            roles: roles.into_iter().map(|role| role.into()).collect(),
+        }
+        .into(),
+    ))
+}
@@ -1,4 +1,14 @@
+pub mod audit;
 pub mod auth;
+pub mod cache;
+pub mod health;
+pub mod impersonation;
+pub mod notification;
 pub mod permission;
 pub mod role;
 pub mod user;
+
+/// Pages needed to list `total` items `limit` at a time, an empty list still has one page
+pub fn pages(total: u64, limit: u64) -> u64 {
+    total.div_ceil(limit.max(1)).max(1)
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use lighter_common::prelude::*;
+
+/// Message carrying a secret that only the owner of an address may see
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Notification {
+    /// Confirms the current email of a user
+    VerifyEmail {
+        user_id: Uuid,
+        email: String,
+        token: String,
+        expired_at: NaiveDateTime,
+    },
+    /// Confirms a pending email change, sent to the new address
+    ChangeEmail {
+        user_id: Uuid,
+        email: String,
+        token: String,
+        expired_at: NaiveDateTime,
+    },
+}
+
+impl Notification {
+    /// Address the notification is delivered to
+    pub fn email(&self) -> &str {
+        match self {
+            Self::VerifyEmail { email, .. } | Self::ChangeEmail { email, .. } => email,
+        }
+    }
+
+    pub fn token(&self) -> &str {
+        match self {
+            Self::VerifyEmail { token, .. } | Self::ChangeEmail { token, .. } => token,
+        }
+    }
+}
+
+/// Out-of-band delivery of notifications, e.g. a mailer
+///
+/// Delivery is best-effort, implementations log their own failures
+pub trait Notifier: Send + Sync {
+    fn notify(&self, notification: Notification);
+}
+
+/// Default notifier, only records that a notification was due
+///
+/// Tokens are never written to the log, deployments that verify emails have to
+/// plug in a notifier that actually delivers them
+pub struct LogNotifier;
+
+impl Notifier for LogNotifier {
+    fn notify(&self, notification: Notification) {
+        match notification {
+            Notification::VerifyEmail { user_id, .. } => tracing::warn!(
+                "Email verification for user {} not delivered, no notifier configured",
+                user_id
+            ),
+            Notification::ChangeEmail { user_id, .. } => tracing::warn!(
+                "Email change confirmation for user {} not delivered, no notifier configured",
+                user_id
+            ),
+        }
+    }
+}
+
+/// Notifier shared through app data, see [`Notifier`]
+#[derive(Clone)]
+pub struct Notifications {
+    notifier: Arc<dyn Notifier>,
+}
+
+impl Notifications {
+    pub fn new(notifier: impl Notifier + 'static) -> Self {
+        Self {
+            notifier: Arc::new(notifier),
+        }
+    }
+
+    pub fn send(&self, notification: Notification) {
+        self.notifier.notify(notification);
+    }
+}
+
+impl Default for Notifications {
+    fn default() -> Self {
+        Self::new(LogNotifier)
+    }
+}
@@ -1,12 +1,15 @@
 use lighter_common::prelude::*;
 
 use crate::entities::v1::permissions::Model;
+use crate::middlewares::v1::auth::Authenticated as Cache;
 
-pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<Success, Error> {
+pub async fn delete(db: &DatabaseConnection, cached: &Cache, id: Uuid) -> Result<Success, Error> {
     match Model::find_by_id(db, id).await? {
         Some(permission) => permission.delete(db).await?,
         None => return Err(NotFound::new("Permission not found").into()),
     };
 
+    cached.remove_by_permission(id).await;
+
     Ok(Success)
 }
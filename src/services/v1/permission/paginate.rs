@@ -42,7 +42,7 @@ pub async fn paginate(
     Ok(PermissionPaginationResponse {
         total,
         page: request.page(),
-        pages: total / request.limit() + 1,
+        pages: crate::services::v1::pages(total, request.limit()),
         data: permissions
             .iter()
             .map(|permission| permission.into())
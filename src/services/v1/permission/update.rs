@@ -1,11 +1,13 @@
 use lighter_common::prelude::*;
 
 use crate::entities::v1::permissions::Model;
+use crate::middlewares::v1::auth::Authenticated as Cache;
 use crate::requests::v1::permission::PermissionRequest;
 use crate::responses::v1::permission::Permission;
 
 pub async fn update(
     db: &DatabaseConnection,
+    cached: &Cache,
     id: Uuid,
     request: PermissionRequest,
 ) -> Result<Permission, Error> {
@@ -26,6 +28,7 @@ pub async fn update(
     };
 
     permission.update(db, name).await?;
+    cached.remove_by_permission(id).await;
 
     Ok(permission.into())
 }
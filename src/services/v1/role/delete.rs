@@ -1,12 +1,17 @@
 use lighter_common::prelude::*;
 
 use crate::entities::v1::roles::Model;
+use crate::middlewares::v1::auth::Authenticated as Cache;
 
-pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<Success, Error> {
-    match Model::find_by_id(db, id).await? {
-        Some(role) => role.delete(db).await?,
+pub async fn delete(db: &DatabaseConnection, cached: &Cache, id: Uuid) -> Result<Success, Error> {
+    let role = match Model::find_by_id(db, id).await? {
+        Some(role) => role,
         None => return Err(NotFound::new("Permission not found").into()),
     };
+    let roles = Model::with_descendants(db, vec![role.id]).await?;
+
+    role.delete(db).await?;
+    cached.remove_by_roles(&roles).await;
 
     Ok(Success)
 }
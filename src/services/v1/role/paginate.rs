@@ -1,16 +1,20 @@
+use actix_web::Either;
 use lighter_common::prelude::*;
 use sea_orm::prelude::*;
 use sea_orm::{ColumnTrait, QueryOrder, QuerySelect};
 
-use crate::entities::v1::roles::{Column, Entity};
+use crate::entities::v1::roles::{Column, Entity, Model};
+use crate::requests::v1::role::RoleInclude;
 use crate::responses::v1::role::{
     RolePaginationOrder, RolePaginationRequest, RolePaginationResponse,
+    RoleWithPermissionsPaginationResponse,
 };
 
 pub async fn paginate(
     db: &DatabaseConnection,
     request: RolePaginationRequest,
-) -> Result<RolePaginationResponse, Error> {
+    include: RoleInclude,
+) -> Result<Either<RolePaginationResponse, Json<RoleWithPermissionsPaginationResponse>>, Error> {
     let mut query = Entity::find();
 
     if let Some(search) = request.search() {
@@ -39,10 +43,29 @@ pub async fn paginate(
 
     let roles = query.all(db).await?;
 
-    Ok(RolePaginationResponse {
+    if !include.permissions() {
+        return Ok(Either::Left(RolePaginationResponse {
+            total,
+            page: request.page(),
+            pages: crate::services::v1::pages(total, request.limit()),
+            data: roles.iter().map(|role| role.into()).collect(),
+        }));
+    }
+
+    let ids = roles.iter().map(|role| role.id).collect();
+    let mut permissions = Model::permissions_of(db, ids).await?;
+
+    Ok(Either::Right(Json(RoleWithPermissionsPaginationResponse {
         total,
         page: request.page(),
-        pages: total / request.limit() + 1,
-        data: roles.iter().map(|role| role.into()).collect(),
-    })
+        pages: crate::services::v1::pages(total, request.limit()),
+        data: roles
+            .into_iter()
+            .map(|role| {
+                let permissions = permissions.remove(&role.id).unwrap_or_default();
+
+                (role, permissions).into()
+            })
+            .collect(),
+    })))
 }
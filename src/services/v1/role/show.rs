@@ -1,11 +1,25 @@
+use actix_web::Either;
 use lighter_common::prelude::*;
 
 use crate::entities::v1::roles::Model;
-use crate::responses::v1::role::Role;
+use crate::requests::v1::role::RoleInclude;
+use crate::responses::v1::role::{Role, RoleWithPermissions};
 
-pub async fn show(db: &DatabaseConnection, id: Uuid) -> Result<Role, Error> {
-    match Model::find_by_id(db, id).await? {
-        Some(role) => Ok(role.into()),
-        None => Err(NotFound::new("Role not found").into()),
+pub async fn show(
+    db: &DatabaseConnection,
+    id: Uuid,
+    include: RoleInclude,
+) -> Result<Either<Role, Json<RoleWithPermissions>>, Error> {
+    let role = match Model::find_by_id(db, id).await? {
+        Some(role) => role,
+        None => return Err(NotFound::new("Role not found").into()),
+    };
+
+    if !include.permissions() {
+        return Ok(Either::Left(role.into()));
     }
+
+    let permissions = role.permissions(db).await?;
+
+    Ok(Either::Right(Json((role, permissions).into())))
 }
@@ -1,13 +1,37 @@
 use lighter_common::prelude::*;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 
+use crate::config::AuthConfig;
+use crate::entities::v1::permissions;
 use crate::entities::v1::roles::Model;
 use crate::requests::v1::role::RoleRequest;
+use crate::requests::v1::UuidListRules;
 use crate::responses::v1::role::Role;
 
-pub async fn store(db: &DatabaseConnection, request: RoleRequest) -> Result<Role, Error> {
+pub async fn store(
+    db: &DatabaseConnection,
+    config: &AuthConfig,
+    request: RoleRequest,
+) -> Result<Role, Error> {
     let mut validation = Validation::new();
     let name = request.name.trim().to_lowercase();
     let code = name.replace(" ", "_").to_uppercase();
+    let permission_errors = UuidListRules {
+        item: "permission",
+        required: false,
+        max: config.max_assignments,
+        unique: true,
+    }
+    .validate(&request.permissions);
+    // malformed lists are rejected below, don't bother looking them up
+    let permissions = if !permission_errors.is_empty() {
+        vec![]
+    } else {
+        permissions::Entity::find()
+            .filter(permissions::Column::Id.is_in(request.permissions.clone()))
+            .all(db)
+            .await?
+    };
 
     if name.is_empty() {
         validation.add("name", "Name is required");
@@ -15,6 +39,30 @@ pub async fn store(db: &DatabaseConnection, request: RoleRequest) -> Result<Role
         validation.add("name", "Name already exist");
     }
 
+    if let Some(parent_id) = request.parent_id {
+        if Model::find_by_id(db, parent_id).await?.is_none() {
+            validation.add("parentId", "Parent role does not exist");
+        }
+    }
+
+    if !permission_errors.is_empty() {
+        for message in permission_errors {
+            validation.add("permissions", message);
+        }
+    } else {
+        for permission_id in &request.permissions {
+            if !permissions
+                .iter()
+                .any(|permission| permission.id == *permission_id)
+            {
+                validation.add(
+                    "permissions",
+                    format!("Permission {} does not exist.", permission_id),
+                );
+            }
+        }
+    }
+
     if !validation.is_empty() {
         return Err(validation.into());
     }
@@ -23,9 +71,10 @@ pub async fn store(db: &DatabaseConnection, request: RoleRequest) -> Result<Role
         id: Uuid::new_v4(),
         code,
         name,
+        parent_id: request.parent_id,
     };
 
-    role.store(db).await?;
+    role.store(db, permissions).await?;
 
     Ok(role.into())
 }
@@ -1,21 +1,73 @@
 use lighter_common::prelude::*;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 
+use crate::config::AuthConfig;
+use crate::entities::v1::permissions;
 use crate::entities::v1::roles::Model;
+use crate::middlewares::v1::auth::Authenticated as Cache;
 use crate::requests::v1::role::RoleRequest;
+use crate::requests::v1::UuidListRules;
 use crate::responses::v1::role::Role;
 
 pub async fn update(
     db: &DatabaseConnection,
+    cached: &Cache,
+    config: &AuthConfig,
     id: Uuid,
     request: RoleRequest,
 ) -> Result<Role, Error> {
     let mut validation = Validation::new();
     let name = request.name.trim().to_lowercase();
+    let permission_errors = UuidListRules {
+        item: "permission",
+        required: false,
+        max: config.max_assignments,
+        unique: true,
+    }
+    .validate(&request.permissions);
+    // malformed lists are rejected below, don't bother looking them up
+    let permissions = if !permission_errors.is_empty() {
+        vec![]
+    } else {
+        permissions::Entity::find()
+            .filter(permissions::Column::Id.is_in(request.permissions.clone()))
+            .all(db)
+            .await?
+    };
 
     if name.is_empty() {
         validation.add("name", "Name is required");
     }
 
+    if let Some(parent_id) = request.parent_id {
+        if Model::find_by_id(db, parent_id).await?.is_none() {
+            validation.add("parentId", "Parent role does not exist");
+        } else if !Model::is_valid_parent(db, id, parent_id).await? {
+            validation.add(
+                "parentId",
+                "Parent role would create a cycle or exceed the maximum depth",
+            );
+        }
+    }
+
+    if !permission_errors.is_empty() {
+        for message in permission_errors {
+            validation.add("permissions", message);
+        }
+    } else {
+        for permission_id in &request.permissions {
+            if !permissions
+                .iter()
+                .any(|permission| permission.id == *permission_id)
+            {
+                validation.add(
+                    "permissions",
+                    format!("Permission {} does not exist.", permission_id),
+                );
+            }
+        }
+    }
+
     if !validation.is_empty() {
         return Err(validation.into());
     }
@@ -25,7 +77,14 @@ pub async fn update(
         None => return Err(NotFound::new("Role not found").into()),
     };
 
-    role.update(db, name).await?;
+    let role = role
+        .update(db, name, request.parent_id, permissions)
+        .await?;
+
+    // permissions are inherited, so sessions holding a descendant role are stale too
+    cached
+        .remove_by_roles(&Model::with_descendants(db, vec![role.id]).await?)
+        .await;
 
     Ok(role.into())
 }
@@ -0,0 +1,55 @@
+use lighter_common::prelude::*;
+
+use crate::config::AuthConfig;
+use crate::entities::v1::users::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+use crate::requests;
+use crate::requests::v1::user::UserPermissionsRequest;
+use crate::responses::v1::user::complete::UserWithPermissionAndRole;
+
+pub async fn assign_permissions(
+    db: &DatabaseConnection,
+    cached: &Cache,
+    config: &AuthConfig,
+    auth: Auth,
+    id: Uuid,
+    request: UserPermissionsRequest,
+) -> Result<Json<UserWithPermissionAndRole>, Error> {
+    if !auth.has_permission("UPDATE_USER") {
+        return Err(Unauthorized::new("Missing UPDATE_USER permission").into());
+    }
+
+    let user = match Model::find_by_id(db, id).await {
+        Some(user) => user,
+        None => return Err(NotFound::new("User not found.").into()),
+    };
+
+    let mut validation = Validation::new();
+    let permissions = requests::v1::find_permissions(
+        db,
+        &mut validation,
+        &request.permissions,
+        config.max_assignments,
+    )
+    .await?;
+
+    if !validation.is_empty() {
+        return Err(validation.into());
+    }
+
+    user.assign_permissions(
+        db,
+        permissions
+            .into_iter()
+            .map(|permission| permission.id)
+            .collect(),
+    )
+    .await?;
+    cached.remove_by_user(user.id, None).await;
+
+    let permissions = user.permissions(db).await?;
+    let roles = user.roles(db).await?;
+
+    Ok(Json((user, permissions, roles).into()))
+}
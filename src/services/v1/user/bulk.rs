@@ -0,0 +1,107 @@
+use actix_web::body::to_bytes;
+use lighter_common::prelude::*;
+use serde_json::Value;
+
+use crate::config::AuthConfig;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::requests::v1::user::UserStoreRequest;
+use crate::responses::v1::user::bulk::{BulkUserResponse, BulkUserResult};
+
+use super::store::store;
+
+/// Users stored at the same time, each one validates, hashes and inserts on its own
+pub const CONCURRENCY: usize = 8;
+
+pub async fn bulk(
+    req: &HttpRequest,
+    db: &DatabaseConnection,
+    config: &AuthConfig,
+    auth: Auth,
+    requests: Vec<UserStoreRequest>,
+) -> Result<BulkUserResponse, Error> {
+    if !auth.has_permission("CREATE_USER") {
+        return Err(Unauthorized::new("Missing CREATE_USER permission").into());
+    }
+
+    let mut validation = Validation::new();
+
+    if requests.is_empty() {
+        validation.add("users", "At least one user is required.");
+    } else if requests.len() > config.max_bulk_users {
+        validation.add(
+            "users",
+            format!(
+                "At most {} users can be imported at once.",
+                config.max_bulk_users
+            ),
+        );
+    }
+
+    if !validation.is_empty() {
+        return Err(validation.into());
+    }
+
+    let mut results = Vec::with_capacity(requests.len());
+    let mut requests = requests.into_iter().enumerate().peekable();
+
+    while requests.peek().is_some() {
+        let handles = requests
+            .by_ref()
+            .take(CONCURRENCY)
+            .map(|(index, request)| {
+                let req = req.clone();
+                let db = db.clone();
+                let config = config.clone();
+
+                (index, actix_web::rt::spawn(item(req, db, config, request)))
+            })
+            .collect::<Vec<_>>();
+
+        for (index, handle) in handles {
+            let (status, body) = match handle.await {
+                Ok(result) => result,
+                Err(e) => {
+                    tracing::error!("Failed to import user {}", index);
+                    tracing::error!("Error: {}", e);
+
+                    (StatusCode::INTERNAL_SERVER_ERROR, Value::Null)
+                }
+            };
+
+            results.push(BulkUserResult {
+                index,
+                success: status.is_success(),
+                status: status.as_u16(),
+                body,
+            });
+        }
+    }
+
+    let created = results.iter().filter(|result| result.success).count() as u64;
+
+    Ok(BulkUserResponse {
+        created,
+        failed: results.len() as u64 - created,
+        results,
+    })
+}
+
+/// Store a single user, answering with what `POST /v1/user` would have
+async fn item(
+    req: HttpRequest,
+    db: DatabaseConnection,
+    config: AuthConfig,
+    request: UserStoreRequest,
+) -> (StatusCode, Value) {
+    let response = store(&db, &config, request)
+        .await
+        .respond_to(&req)
+        .map_into_boxed_body();
+    let status = response.status();
+    let body = match to_bytes(response.into_body()).await {
+        Ok(body) => serde_json::from_slice(&body).unwrap_or(Value::Null),
+        Err(_) => Value::Null,
+    };
+
+    (status, body)
+}
@@ -0,0 +1,62 @@
+use lighter_common::{base58, prelude::*};
+
+use crate::config::AuthConfig;
+use crate::entities::v1::users::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::EmailVerification;
+use crate::requests::v1::user::UserEmailRequest;
+use crate::responses::v1::auth::VerificationSent;
+use crate::services::v1::notification::{Notification, Notifications};
+
+pub async fn change_email(
+    db: &DatabaseConnection,
+    config: &AuthConfig,
+    verification: &EmailVerification,
+    notifications: &Notifications,
+    auth: Auth,
+    id: Uuid,
+    request: UserEmailRequest,
+) -> Result<VerificationSent, Error> {
+    if auth.user.id != id && !auth.has_permission("UPDATE_USER") {
+        return Err(Unauthorized::new("Missing UPDATE_USER permission").into());
+    }
+
+    let user = match Model::find_by_id(db, id).await {
+        None => return Err(NotFound::new("User not found.").into()),
+        Some(user) => user,
+    };
+
+    let mut validation = Validation::new();
+    let email = Model::normalize_email(&request.email, config.canonicalize_gmail);
+
+    if email.is_empty() {
+        validation.add("email", "Email is required.");
+    } else if !Model::is_valid_email(&email) {
+        validation.add("email", "Email is invalid.");
+    } else if email == user.email {
+        validation.add("email", "Email is unchanged.");
+    } else if Model::email_exists(db, &email).await {
+        validation.add("email", "Email already exists.");
+    }
+
+    if !validation.is_empty() {
+        return Err(validation.into());
+    }
+
+    // the current email stays in use until the new one is confirmed
+    let (token, expired_at) = verification
+        .issue_email_change(user.id, email.clone(), config.email_verification_lifetime)
+        .await;
+
+    tracing::info!("Email change token issued for user {}", user.id);
+
+    // confirming proves control of the new address, so only it gets the token
+    notifications.send(Notification::ChangeEmail {
+        user_id: user.id,
+        email,
+        token: base58::to_string(token),
+        expired_at,
+    });
+
+    Ok(VerificationSent { expired_at })
+}
@@ -1,12 +1,15 @@
 use lighter_common::prelude::*;
 
 use crate::entities::v1::users::Model;
+use crate::middlewares::v1::auth::Authenticated as Cache;
 
-pub async fn delete(db: &DatabaseConnection, id: Uuid) -> Result<Success, Error> {
+pub async fn delete(db: &DatabaseConnection, cached: &Cache, id: Uuid) -> Result<Success, Error> {
     match Model::find_by_id(db, id).await {
         None => return Err(NotFound::new("User not found.").into()),
         Some(user) => user.soft_delete(db).await?,
     };
 
+    cached.remove_by_user(id, None).await;
+
     Ok(Success)
 }
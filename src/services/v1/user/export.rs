@@ -0,0 +1,58 @@
+use lighter_common::prelude::*;
+use sea_orm::prelude::*;
+use sea_orm::{PaginatorTrait, QueryOrder};
+
+use crate::entities::v1::users::Model;
+use crate::entities::v1::{audit_logs, tokens};
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::responses::v1::user::export::UserExport;
+
+/// Audit entries fetched per query while assembling the export
+pub const PAGE_SIZE: u64 = 100;
+
+pub async fn export(db: &DatabaseConnection, auth: Auth, id: Uuid) -> Result<UserExport, Error> {
+    if auth.user.id != id && !auth.has_permission("READ_USER") {
+        return Err(Unauthorized::new("Missing READ_USER permission").into());
+    }
+
+    let user = match Model::find_by_id(db, id).await {
+        None => return Err(NotFound::new("User not found.").into()),
+        Some(user) => user,
+    };
+
+    let permissions = user.permissions(db).await?;
+    let roles = user.roles(db).await?;
+    let sessions = tokens::Model::active(db, id).await?;
+    let mut pages = audit_logs::Entity::find()
+        .filter(
+            Condition::any()
+                .add(audit_logs::Column::ActorUserId.eq(id))
+                .add(
+                    Condition::all()
+                        .add(audit_logs::Column::TargetType.eq("user"))
+                        .add(audit_logs::Column::TargetId.eq(id)),
+                ),
+        )
+        .order_by_asc(audit_logs::Column::CreatedAt)
+        .order_by_asc(audit_logs::Column::Id)
+        .paginate(db, PAGE_SIZE);
+    let mut logs = vec![];
+
+    while let Some(page) = pages.fetch_and_next().await? {
+        logs.extend(page.into_iter().map(|log| log.into()));
+    }
+
+    Ok(UserExport {
+        created_at: user.created_at,
+        last_login_at: user.last_login_at,
+        profile: user.into(),
+        roles: roles.into_iter().map(|role| role.into()).collect(),
+        permissions: permissions
+            .into_iter()
+            .map(|permission| permission.into())
+            .collect(),
+        sessions: sessions.into_iter().map(|token| token.into()).collect(),
+        audit_logs: logs,
+        exported_at: now(),
+    })
+}
@@ -1,5 +1,13 @@
+pub mod assign_permissions;
+pub mod bulk;
+pub mod change_email;
 pub mod delete;
+pub mod export;
 pub mod paginate;
+pub mod purge;
+pub mod restore;
+pub mod revoke_permissions;
+pub mod session_count;
 pub mod show;
 pub mod store;
 pub mod update_general_information;
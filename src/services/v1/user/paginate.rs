@@ -3,18 +3,31 @@ use sea_orm::prelude::*;
 use sea_orm::{ColumnTrait, QueryOrder, QuerySelect};
 
 use crate::entities::v1::users::{Column, Entity};
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::requests::v1::user::UserPaginationFilter;
 use crate::responses::v1::user::simple::{
     UserPaginationOrder, UserPaginationRequest, UserPaginationResponse,
 };
 
 pub async fn paginate(
     db: &DatabaseConnection,
+    auth: Auth,
     request: UserPaginationRequest,
+    filter: UserPaginationFilter,
 ) -> Result<UserPaginationResponse, Error> {
-    let mut query = Entity::find().filter(Column::DeletedAt.is_null());
+    if !auth.has_permission("READ_USER") {
+        return Err(Unauthorized::new("Missing READ_USER permission").into());
+    }
+
+    let mut query = Entity::find();
+
+    if !filter.include_deleted {
+        query = query.filter(Column::DeletedAt.is_null());
+    }
 
     if let Some(search) = request.search() {
-        let search = format!("%{}%", search);
+        // name, email and username are stored lowercase
+        let search = format!("%{}%", search.trim().to_lowercase());
 
         query = query.filter(
             Condition::any()
@@ -45,7 +58,7 @@ pub async fn paginate(
     Ok(UserPaginationResponse {
         total,
         page: request.page(),
-        pages: total / request.limit() + 1,
+        pages: crate::services::v1::pages(total, request.limit()),
         data: users.iter().map(|user| user.into()).collect(),
     })
 }
@@ -0,0 +1,32 @@
+use lighter_common::prelude::*;
+
+use crate::entities::v1::users::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+
+pub async fn purge(
+    db: &DatabaseConnection,
+    cached: &Cache,
+    auth: Option<Auth>,
+    id: Uuid,
+) -> Result<Success, Error> {
+    match auth {
+        Some(auth) if auth.has_permission("DELETE_USER") => {}
+        _ => return Err(Unauthorized::new("Missing DELETE_USER permission").into()),
+    }
+
+    let user = match Model::find_by_id_with_deleted(db, id).await {
+        None => return Err(NotFound::new("User not found.").into()),
+        Some(user) => user,
+    };
+
+    // only soft deleted users can be purged, so erasing takes two deliberate steps
+    if user.deleted_at.is_none() {
+        return Err(BadRequest::new("User must be deleted before it can be purged.").into());
+    }
+
+    user.purge(db).await?;
+    cached.remove_by_user(id, None).await;
+
+    Ok(Success)
+}
@@ -0,0 +1,25 @@
+use lighter_common::prelude::*;
+
+use crate::entities::v1::users::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::responses::v1::user::complete::UserWithPermissionAndRole;
+
+pub async fn restore(
+    db: &DatabaseConnection,
+    auth: Auth,
+    id: Uuid,
+) -> Result<Json<UserWithPermissionAndRole>, Error> {
+    if !auth.has_permission("UPDATE_USER") {
+        return Err(Unauthorized::new("Missing UPDATE_USER permission").into());
+    }
+
+    let user = match Model::find_by_id_with_deleted(db, id).await {
+        Some(user) => user.restore(db).await?,
+        None => return Err(NotFound::new("User not found.").into()),
+    };
+
+    let permissions = user.permissions(db).await?;
+    let roles = user.roles(db).await?;
+
+    Ok(Json((user, permissions, roles).into()))
+}
@@ -0,0 +1,32 @@
+use lighter_common::prelude::*;
+
+use crate::entities::v1::users::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::middlewares::v1::auth::Authenticated as Cache;
+use crate::requests::v1::user::UserPermissionsRequest;
+use crate::responses::v1::user::complete::UserWithPermissionAndRole;
+
+pub async fn revoke_permissions(
+    db: &DatabaseConnection,
+    cached: &Cache,
+    auth: Auth,
+    id: Uuid,
+    request: UserPermissionsRequest,
+) -> Result<Json<UserWithPermissionAndRole>, Error> {
+    if !auth.has_permission("UPDATE_USER") {
+        return Err(Unauthorized::new("Missing UPDATE_USER permission").into());
+    }
+
+    let user = match Model::find_by_id(db, id).await {
+        Some(user) => user,
+        None => return Err(NotFound::new("User not found.").into()),
+    };
+
+    user.revoke_permissions(db, request.permissions).await?;
+    cached.remove_by_user(user.id, None).await;
+
+    let permissions = user.permissions(db).await?;
+    let roles = user.roles(db).await?;
+
+    Ok(Json((user, permissions, roles).into()))
+}
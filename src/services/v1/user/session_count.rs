@@ -0,0 +1,24 @@
+use lighter_common::prelude::*;
+
+use crate::entities::v1::tokens;
+use crate::entities::v1::users::Model;
+use crate::middlewares::v1::auth::internal::Auth;
+use crate::responses::v1::auth::SessionCount;
+
+pub async fn session_count(
+    db: &DatabaseConnection,
+    auth: Auth,
+    id: Uuid,
+) -> Result<SessionCount, Error> {
+    if auth.user.id != id && !auth.has_permission("READ_USER") {
+        return Err(Unauthorized::new("Missing READ_USER permission").into());
+    }
+
+    if Model::find_by_id(db, id).await.is_none() {
+        return Err(NotFound::new("User not found.").into());
+    }
+
+    let count = tokens::Model::count_active(db, id).await?;
+
+    Ok(SessionCount { count })
+}
@@ -1,31 +1,38 @@
 use actix_web::web::Json;
+use actix_web::Either;
 use lighter_common::prelude::*;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::TransactionError;
 
+use crate::config::AuthConfig;
 use crate::entities::v1::users::Model;
 use crate::entities::v1::{permissions, roles};
+use crate::requests;
 use crate::requests::v1::user::UserStoreRequest;
+use crate::responses::v1::conflict::Conflict;
 use crate::responses::v1::user::complete::UserWithPermissionAndRole;
 
 pub async fn store(
     db: &DatabaseConnection,
+    config: &AuthConfig,
     request: UserStoreRequest,
-) -> Result<Json<UserWithPermissionAndRole>, Error> {
+) -> Result<Either<Json<UserWithPermissionAndRole>, Conflict>, Error> {
     let mut validation = Validation::new();
     let name = request.name.trim().to_lowercase();
-    let email = request.email.trim().to_lowercase();
-    let username = request.username.trim().to_lowercase();
+    let email = Model::normalize_email(&request.email, config.canonicalize_gmail);
+    let username = Model::normalize_username(&request.username);
     let password = request.password;
     let password_confirmation = request.password_confirmation;
     let profile_photo_id = request.profile_photo_id.map(|id| id.trim().to_string());
-    let permissions = permissions::Entity::find()
-        .filter(permissions::Column::Id.is_in(request.permissions.clone()))
-        .all(db)
-        .await?;
-    let roles = roles::Entity::find()
-        .filter(roles::Column::Id.is_in(request.roles.clone()))
-        .all(db)
-        .await?;
+    let permissions = requests::v1::find_permissions(
+        db,
+        &mut validation,
+        &request.permissions,
+        config.max_assignments,
+    )
+    .await?;
+    let roles =
+        requests::v1::find_roles(db, &mut validation, &request.roles, config.max_assignments)
+            .await?;
 
     if name.is_empty() {
         validation.add("name", "Name is required.");
@@ -33,59 +40,64 @@ pub async fn store(
 
     if email.is_empty() {
         validation.add("email", "Email is required.");
-    } else {
-        if Model::email_exists(db, &email).await {
-            validation.add("email", "Email already exists.");
-        }
+    } else if !Model::is_valid_email(&email) {
+        validation.add("email", "Email is invalid.");
     }
 
     if username.is_empty() {
         validation.add("username", "Username is required.");
-    } else {
-        if Model::username_exists(db, &username).await {
-            validation.add("username", "Username already exists.");
-        }
+    } else if !Model::is_valid_username(&request.username) {
+        validation.add(
+            "username",
+            "Username may only contain letters, digits, \".\", \"_\" and \"-\".",
+        );
     }
 
     if password.is_empty() {
         validation.add("password", "Password is required.");
-    } else if password.len() < 8 {
-        validation.add("password", "Password must be at least 8 characters.");
+    } else {
+        for message in config.password_policy.validate(&password, &username) {
+            validation.add("password", message);
+        }
     }
 
     if password != password_confirmation {
         validation.add(
-            "password_confirmation",
+            "passwordConfirmation",
             "Password confirmation does not match.",
         );
     }
 
-    if !request.permissions.is_empty() {
-        for permission_id in &request.permissions {
-            if !permissions
-                .iter()
-                .any(|permission| permission.id == *permission_id)
-            {
-                validation.add(
-                    "permissions",
-                    format!("Permission {} does not exist.", permission_id),
-                );
-            }
-        }
+    if !validation.is_empty() {
+        return Err(validation.into());
     }
 
-    if !request.roles.is_empty() {
-        for role_id in &request.roles {
-            if !roles.iter().any(|role| role.id == *role_id) {
-                validation.add("roles", format!("Role {} does not exist.", role_id));
-            }
-        }
+    if Model::email_exists(db, &email).await {
+        return Ok(Either::Right(Conflict::new(
+            "email",
+            "Email already exists.",
+        )));
     }
 
-    if !validation.is_empty() {
-        return Err(validation.into());
+    if Model::username_exists(db, &username).await {
+        return Ok(Either::Right(Conflict::new(
+            "username",
+            "Username already exists.",
+        )));
     }
 
+    // explicit assignments replace the defaults, roles and permissions independently
+    let permissions = if request.permissions.is_empty() {
+        permissions::Model::find_by_codes(db, &config.default_permissions).await?
+    } else {
+        permissions
+    };
+    let roles = if request.roles.is_empty() {
+        roles::Model::find_by_codes(db, &config.default_roles).await?
+    } else {
+        roles
+    };
+
     let id = Uuid::new_v4();
     let hash = Hash::make(id, &password);
     let password = hash.to_string();
@@ -100,9 +112,27 @@ pub async fn store(
         created_at: now(),
         updated_at: now(),
         deleted_at: None,
+        last_login_at: None,
     };
 
-    model.store(db, permissions.clone(), roles.clone()).await?;
+    // a concurrent store may still win the race past the checks above
+    if let Err(e) = model.store(db, permissions.clone(), roles.clone()).await {
+        let e = match e {
+            TransactionError::Connection(e) | TransactionError::Transaction(e) => e,
+        };
+
+        return match Model::unique_violation(&e) {
+            Some("email") => Ok(Either::Right(Conflict::new(
+                "email",
+                "Email already exists.",
+            ))),
+            Some(_) => Ok(Either::Right(Conflict::new(
+                "username",
+                "Username already exists.",
+            ))),
+            None => Err(e.into()),
+        };
+    }
 
-    Ok(Json((model, permissions, roles).into()))
+    Ok(Either::Left(Json((model, permissions, roles).into())))
 }
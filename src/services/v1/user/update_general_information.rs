@@ -1,29 +1,33 @@
 use lighter_common::prelude::*;
-use sea_orm::prelude::*;
-use sea_orm::ColumnTrait;
 
+use crate::config::AuthConfig;
 use crate::entities::v1::users::Model;
-use crate::entities::v1::{permissions, roles};
+use crate::middlewares::v1::auth::Authenticated as Cache;
+use crate::requests;
 use crate::requests::v1::user::UserUpdateGeneralInformationRequest;
 
 pub async fn update(
     db: &DatabaseConnection,
+    cached: &Cache,
+    config: &AuthConfig,
     id: Uuid,
     request: UserUpdateGeneralInformationRequest,
 ) -> Result<Success, Error> {
     let mut validation = Validation::new();
     let name = request.name.trim().to_lowercase();
-    let email = request.email.trim().to_lowercase();
-    let username = request.username.trim().to_lowercase();
+    let email = Model::normalize_email(&request.email, config.canonicalize_gmail);
+    let username = Model::normalize_username(&request.username);
     let profile_photo_id = request.profile_photo_id.map(|id| id.trim().to_string());
-    let permissions = permissions::Entity::find()
-        .filter(permissions::Column::Id.is_in(request.permissions.clone()))
-        .all(db)
-        .await?;
-    let roles = roles::Entity::find()
-        .filter(roles::Column::Id.is_in(request.roles.clone()))
-        .all(db)
-        .await?;
+    let permissions = requests::v1::find_permissions(
+        db,
+        &mut validation,
+        &request.permissions,
+        config.max_assignments,
+    )
+    .await?;
+    let roles =
+        requests::v1::find_roles(db, &mut validation, &request.roles, config.max_assignments)
+            .await?;
 
     if name.is_empty() {
         validation.add("name", "Name is required.");
@@ -31,32 +35,17 @@ pub async fn update(
 
     if email.is_empty() {
         validation.add("email", "Email is required.");
+    } else if !Model::is_valid_email(&email) {
+        validation.add("email", "Email is invalid.");
     }
 
     if username.is_empty() {
         validation.add("username", "Username is required.");
-    }
-
-    if !request.permissions.is_empty() {
-        for permission_id in &request.permissions {
-            if !permissions
-                .iter()
-                .any(|permission| permission.id == *permission_id)
-            {
-                validation.add(
-                    "permissions",
-                    format!("Permission {} does not exist.", permission_id),
-                );
-            }
-        }
-    }
-
-    if !request.roles.is_empty() {
-        for role_id in &request.roles {
-            if !roles.iter().any(|role| role.id == *role_id) {
-                validation.add("roles", format!("Role {} does not exist.", role_id));
-            }
-        }
+    } else if !Model::is_valid_username(&request.username) {
+        validation.add(
+            "username",
+            "Username may only contain letters, digits, \".\", \"_\" and \"-\".",
+        );
     }
 
     if !validation.is_empty() {
@@ -68,6 +57,16 @@ pub async fn update(
         Some(user) => user,
     };
 
+    // a new address has to be confirmed first, the pending flow owns email changes
+    if email != user.email {
+        validation.add(
+            "email",
+            "Email can only be changed through POST /v1/user/{id}/email.",
+        );
+
+        return Err(validation.into());
+    }
+
     user.update_general_information(
         db,
         name,
@@ -80,5 +79,7 @@ pub async fn update(
     )
     .await?;
 
+    cached.remove_by_user(id, None).await;
+
     Ok(Success)
 }
@@ -1,10 +1,13 @@
 use lighter_common::prelude::*;
 
+use crate::config::AuthConfig;
+use crate::entities::v1::password_histories;
 use crate::entities::v1::users::Model;
 use crate::requests::v1::user::UserUpdatePasswordRequest;
 
 pub async fn update(
     db: &DatabaseConnection,
+    config: &AuthConfig,
     id: Uuid,
     request: UserUpdatePasswordRequest,
 ) -> Result<Success, Error> {
@@ -14,23 +17,20 @@ pub async fn update(
     let password_confirmation = request.password_confirmation;
 
     if current_password.is_empty() {
-        validation.add("current_password", "Current password is required.");
+        validation.add("currentPassword", "Current password is required.");
     }
 
     if new_password.is_empty() {
-        validation.add("new_password", "New password is required.");
+        validation.add("newPassword", "New password is required.");
     }
 
     if password_confirmation.is_empty() {
-        validation.add(
-            "password_confirmation",
-            "Password confirmation is required.",
-        );
+        validation.add("passwordConfirmation", "Password confirmation is required.");
     }
 
     if new_password != password_confirmation {
         validation.add(
-            "password_confirmation",
+            "passwordConfirmation",
             "Password confirmation does not match.",
         );
     }
@@ -45,15 +45,42 @@ pub async fn update(
     };
 
     if !Hash::from(&user.password).verify(id, &current_password) {
-        validation.add("current_password", "Current password is incorrect.");
+        validation.add("currentPassword", "Current password is incorrect.");
+    }
+
+    for message in config
+        .password_policy
+        .validate(&new_password, &user.username)
+    {
+        validation.add("newPassword", message);
+    }
+
+    if config.password_history_size > 0 {
+        let history =
+            password_histories::Model::recent(db, id, config.password_history_size).await?;
+        let reused = Hash::from(&user.password).verify(id, &new_password)
+            || history
+                .iter()
+                .any(|previous| Hash::from(&previous.password).verify(id, &new_password));
+
+        if reused {
+            validation.add(
+                "newPassword",
+                "New password must not match a recently used password.",
+            );
+        }
     }
 
     if !validation.is_empty() {
         return Err(validation.into());
     }
 
-    user.update_password(db, Hash::make(id, &new_password))
-        .await?;
+    user.update_password(
+        db,
+        Hash::make(id, &new_password),
+        config.password_history_size,
+    )
+    .await?;
 
     Ok(Success)
 }
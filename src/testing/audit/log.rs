@@ -0,0 +1,133 @@
+#[test]
+pub async fn audit_login_and_permission_grant() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::entities::v1::{audit_logs, permissions, users};
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::requests::v1::user::UserPermissionsRequest;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let id = Uuid::new_v4();
+    let user = users::Model {
+        id,
+        name: "audited".to_string(),
+        email: format!("{}@local", id),
+        email_verified_at: Some(now()),
+        username: id.simple().to_string(),
+        password: Hash::make(id, "password").to_string(),
+        profile_photo_id: None,
+        created_at: now(),
+        updated_at: now(),
+        deleted_at: None,
+        last_login_at: None,
+    }
+    .store(&db, vec![], vec![])
+    .await?;
+
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/login")
+        .set_json(&LoginRequest {
+            email_or_username: user.username.clone(),
+            password: "password".to_string(),
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let logs = audit_logs::Entity::find()
+        .filter(audit_logs::Column::TargetId.eq(user.id))
+        .filter(audit_logs::Column::Action.eq("login"))
+        .all(&db)
+        .await?;
+
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].actor_user_id, Some(user.id));
+
+    let suffix = Uuid::new_v4().simple().to_string().to_uppercase();
+    let permission = permissions::Model {
+        id: Uuid::new_v4(),
+        code: format!("AUDITED_{}", suffix),
+        name: format!("audited {}", suffix),
+    }
+    .store(&db)
+    .await?;
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri(format!("/v1/user/{}/permissions", user.id).as_str())
+        .set_json(&UserPermissionsRequest {
+            permissions: vec![permission.id],
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let logs = audit_logs::Entity::find()
+        .filter(audit_logs::Column::TargetId.eq(user.id))
+        .filter(audit_logs::Column::Action.eq("assign_permissions"))
+        .all(&db)
+        .await?;
+
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].actor_user_id, Some(Uuid::from_u128(0)));
+
+    Ok(())
+}
+
+#[test]
+pub async fn paginate_requires_permission() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::{base58, prelude::*};
+
+    use crate::entities::v1::users;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .uri("/v1/audit")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let id = Uuid::new_v4();
+    let user = users::Model {
+        id,
+        name: "not an auditor".to_string(),
+        email: format!("{}@local", id),
+        email_verified_at: None,
+        username: id.simple().to_string(),
+        password: Hash::make(id, "password").to_string(),
+        profile_photo_id: None,
+        created_at: now(),
+        updated_at: now(),
+        deleted_at: None,
+        last_login_at: None,
+    }
+    .store(&db, vec![], vec![])
+    .await?;
+    let session = base58::to_string(user.generate_token(&db, None).await?.id);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", session)))
+        .uri("/v1/audit")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+#[test]
+pub async fn api_key() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::body::MessageBody;
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::requests::v1::auth::ApiKeyRequest;
+    use crate::responses::v1::auth::ApiKey;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let token = token(&db).await;
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .method(Method::POST)
+        .uri("/v1/auth/api-keys")
+        .set_json(&ApiKeyRequest {
+            scopes: vec!["read_user".to_string()],
+            expired_at: None,
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+    let status = response.status();
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+    assert_eq!(status, StatusCode::CREATED, "{:?}", body);
+
+    let key = serde_json::from_slice::<ApiKey>(&body)?;
+
+    assert_eq!(key.scopes, vec!["READ_USER".to_string()]);
+
+    // access within scope
+    let request = TestRequest::default()
+        .insert_header(("X-Api-Key", key.key.clone()))
+        .uri("/v1/user")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // access outside of scope
+    let request = TestRequest::default()
+        .insert_header(("X-Api-Key", key.key.clone()))
+        .method(Method::POST)
+        .uri(format!("/v1/user/{}/restore", Uuid::from_u128(0)).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // tampered secret
+    let (prefix, _) = key.key.split_once('.').unwrap();
+    let request = TestRequest::default()
+        .insert_header(("X-Api-Key", format!("{}.tampered", prefix)))
+        .uri("/v1/user")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .method(Method::DELETE)
+        .uri(format!("/v1/auth/api-keys/{}", key.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::default()
+        .insert_header(("X-Api-Key", key.key.clone()))
+        .uri("/v1/user")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[test]
+pub async fn api_key_scope_not_granted() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::{base58, prelude::*};
+
+    use crate::entities::v1::users;
+    use crate::requests::v1::auth::ApiKeyRequest;
+
+    let (service, db) = crate::service!();
+    let id = Uuid::new_v4();
+    let user = users::Model {
+        id,
+        name: "no permissions".to_string(),
+        email: format!("{}@local", id),
+        email_verified_at: None,
+        username: id.simple().to_string(),
+        password: Hash::make(id, "password").to_string(),
+        profile_photo_id: None,
+        created_at: now(),
+        updated_at: now(),
+        deleted_at: None,
+        last_login_at: None,
+    }
+    .store(&db, vec![], vec![])
+    .await?;
+    let session = base58::to_string(user.generate_token(&db, None).await?.id);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", session)))
+        .method(Method::POST)
+        .uri("/v1/auth/api-keys")
+        .set_json(&ApiKeyRequest {
+            scopes: vec!["READ_USER".to_string()],
+            expired_at: None,
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    Ok(())
+}
+
+#[test]
+pub async fn api_key_roles_and_dormant() -> Result<(), lighter_common::prelude::Error> {
+    use std::time::Duration;
+
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ActiveModelTrait, Set};
+    use serde_json::Value;
+
+    use crate::config::AuthConfig;
+    use crate::entities::v1::{api_keys, users};
+    use crate::testing::instance::user;
+
+    let (service, db) = crate::service!(AuthConfig {
+        // 90 days
+        dormant_after: Some(Duration::from_secs(60 * 60 * 24 * 90)),
+        ..AuthConfig::default()
+    });
+
+    // the owner's roles don't ride along with a scoped key
+    let root = users::Model::find_by_id(&db, Uuid::from_u128(0))
+        .await
+        .unwrap();
+
+    assert!(!root.roles(&db).await?.is_empty());
+
+    let (_, key) =
+        api_keys::Model::generate(&db, root.id, vec!["READ_USER".to_string()], None).await?;
+    let request = TestRequest::default()
+        .insert_header(("X-Api-Key", key))
+        .uri("/user")
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: Value = read_body_json(response).await;
+    let permissions = body["permissions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|permission| permission["code"].as_str().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(permissions, vec!["READ_USER"]);
+    assert!(body["roles"].as_array().unwrap().is_empty());
+
+    // keys of dormant accounts are rejected like their sessions
+    let model = user(&db, &["READ_USER"]).await;
+    let mut active = users::ActiveModel::from(model.clone());
+
+    active.last_login_at = Set(Some(now() - Duration::from_secs(60 * 60 * 24 * 120)));
+    active.update(&db).await?;
+
+    let (_, key) =
+        api_keys::Model::generate(&db, model.id, vec!["READ_USER".to_string()], None).await?;
+    let request = TestRequest::default()
+        .insert_header(("X-Api-Key", key))
+        .uri("/user")
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[test]
+pub async fn api_key_touch_throttled() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    use crate::entities::v1::api_keys;
+    use crate::middlewares::v1::auth::internal::TOUCH_THROTTLE;
+    use crate::testing::instance::user;
+
+    let (service, db) = crate::service!();
+    let owner = user(&db, &["READ_USER"]).await;
+    let (model, key) =
+        api_keys::Model::generate(&db, owner.id, vec!["READ_USER".to_string()], None).await?;
+    let stale = now() - TOUCH_THROTTLE * 2;
+    let mut active = api_keys::ActiveModel::from(model.clone());
+
+    active.last_used_at = Set(Some(stale));
+    active.update(&db).await?;
+
+    let request = || {
+        TestRequest::default()
+            .insert_header(("X-Api-Key", key.clone()))
+            .uri("/user")
+            .to_request()
+    };
+    let last_used_at = || {
+        let db = db.clone();
+
+        async move {
+            api_keys::Entity::find_by_id(model.id)
+                .one(&db)
+                .await
+                .unwrap()
+                .unwrap()
+                .last_used_at
+                .unwrap()
+        }
+    };
+
+    // a stale timestamp gets refreshed
+    let response = call_service(&service, request()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let touched = last_used_at().await;
+
+    assert!(touched > stale);
+
+    // requests within the throttle window don't write again
+    for _ in 0..3 {
+        let response = call_service(&service, request()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    assert_eq!(last_used_at().await, touched);
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+#[test]
+pub async fn can() -> Result<(), lighter_common::prelude::Error> {
+    use std::collections::BTreeMap;
+
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body_json};
+    use lighter_common::prelude::*;
+
+    use crate::requests::v1::auth::PermissionCheckRequest;
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::authed_request;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .permission("direct")
+        .permission("inherited")
+        .permission("missing")
+        .role("member", &["inherited"])
+        .user("checker", &["member"], &["direct"])
+        .build(&db)
+        .await;
+    let code = |name: &str| world.permission(name).code.clone();
+    let request = authed_request(&db, world.user("checker"))
+        .await
+        .method(Method::POST)
+        .uri("/v1/auth/can")
+        .set_json(&PermissionCheckRequest {
+            permissions: vec![
+                code("direct"),
+                code("inherited"),
+                code("missing"),
+                "NOT_A_PERMISSION".to_string(),
+            ],
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let checked: BTreeMap<String, bool> = read_body_json(response).await;
+
+    assert_eq!(checked.len(), 4);
+    assert!(checked[&code("direct")]);
+    assert!(checked[&code("inherited")]);
+    assert!(!checked[&code("missing")]);
+    assert!(!checked["NOT_A_PERMISSION"]);
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+#[test]
+pub async fn dormant() -> Result<(), lighter_common::prelude::Error> {
+    use std::time::Duration;
+
+    use actix_web::test::{call_service, read_body, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ActiveModelTrait, Set};
+
+    use crate::config::AuthConfig;
+    use crate::entities::v1::users;
+    use crate::testing::instance::user;
+
+    let (service, db) = crate::service!(AuthConfig {
+        // 90 days
+        dormant_after: Some(Duration::from_secs(60 * 60 * 24 * 90)),
+        ..AuthConfig::default()
+    });
+
+    for (days, allowed) in [(120, false), (1, true)] {
+        let model = user(&db, &[]).await;
+        let mut active = users::ActiveModel::from(model.clone());
+
+        active.last_login_at = Set(Some(now() - Duration::from_secs(60 * 60 * 24 * days)));
+
+        let model = active.update(&db).await?;
+        let token = model.generate_token(&db, None).await?;
+        let request = TestRequest::default()
+            .insert_header((
+                "Authorization",
+                format!("Bearer {}", base58::to_string(token.id)),
+            ))
+            .uri("/user")
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        if allowed {
+            assert_eq!(response.status(), StatusCode::OK);
+        } else {
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+            let body = read_body(response).await;
+
+            assert!(String::from_utf8_lossy(&body).contains("ACCOUNT_DORMANT"));
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub async fn disabled_by_default() {
+    use crate::config::AuthConfig;
+
+    let config = AuthConfig::default();
+
+    assert!(config.dormant_after.is_none());
+    assert!(!config.is_dormant(Some(lighter_common::prelude::NaiveDateTime::MIN)));
+}
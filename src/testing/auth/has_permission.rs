@@ -0,0 +1,41 @@
+#[test]
+pub async fn has_permission() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, read_body_json};
+
+    use crate::middlewares::v1::auth::internal::Auth;
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::authed_request;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .permission("direct")
+        .permission("inherited")
+        .permission("missing")
+        .role("parent", &["inherited"])
+        .child_role("child", Some("parent"), &[])
+        .user("checker", &["child"], &["direct"])
+        .build(&db)
+        .await;
+    let request = authed_request(&db, world.user("checker"))
+        .await
+        .uri("/user")
+        .to_request();
+    let auth: Auth = read_body_json(call_service(&service, request).await).await;
+    let direct = world.permission("direct").code.as_str();
+    let inherited = world.permission("inherited").code.as_str();
+    let missing = world.permission("missing").code.as_str();
+
+    assert!(auth.has_permission(direct));
+    assert!(auth.has_permission(inherited));
+    assert!(!auth.has_permission(missing));
+
+    assert!(auth.has_any(&[missing, inherited]));
+    assert!(!auth.has_any(&[missing]));
+    assert!(!auth.has_any(&[]));
+
+    assert!(auth.has_all(&[direct, inherited]));
+    assert!(!auth.has_all(&[direct, missing]));
+    assert!(auth.has_all(&[]));
+
+    Ok(())
+}
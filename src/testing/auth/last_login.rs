@@ -0,0 +1,59 @@
+#[test]
+pub async fn last_login() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::EntityTrait;
+
+    use crate::entities::v1::users;
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::testing::instance::user;
+
+    async fn last_login_at(db: &DatabaseConnection, id: Uuid) -> Option<NaiveDateTime> {
+        let user = users::Entity::find_by_id(id).one(db).await.unwrap();
+
+        user.unwrap().last_login_at
+    }
+
+    let (service, db) = crate::service!();
+    let model = user(&db, &[]).await;
+    let login = |password: &str| {
+        TestRequest::default()
+            .method(Method::POST)
+            .uri("/login")
+            .set_json(&LoginRequest {
+                email_or_username: model.username.clone(),
+                password: password.to_string(),
+            })
+            .to_request()
+    };
+
+    assert!(last_login_at(&db, model.id).await.is_none());
+
+    let response = call_service(&service, login("wrong password")).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(last_login_at(&db, model.id).await.is_none());
+
+    let response = call_service(&service, login("password")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let first = last_login_at(&db, model.id).await;
+
+    assert!(first.is_some());
+
+    let response = call_service(&service, login("wrong password")).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(last_login_at(&db, model.id).await, first);
+
+    actix_web::rt::time::sleep(std::time::Duration::from_millis(10)).await;
+
+    let response = call_service(&service, login("password")).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(last_login_at(&db, model.id).await > first);
+
+    Ok(())
+}
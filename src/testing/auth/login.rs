@@ -0,0 +1,153 @@
+#[test]
+pub async fn login_failure_timing() -> Result<(), lighter_common::prelude::Error> {
+    use std::time::{Duration, Instant};
+
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::testing::instance::user;
+
+    let (service, db) = crate::service!();
+    let user = user(&db, &[]).await;
+    let mut elapsed = [Duration::ZERO; 2];
+
+    for _ in 0..10 {
+        for (i, email_or_username) in [user.username.clone(), Uuid::new_v4().simple().to_string()]
+            .into_iter()
+            .enumerate()
+        {
+            let request = TestRequest::default()
+                .method(Method::POST)
+                .uri("/login")
+                .set_json(&LoginRequest {
+                    email_or_username,
+                    password: "wrong password".to_string(),
+                })
+                .to_request();
+
+            let start = Instant::now();
+            let response = call_service(&service, request).await;
+
+            elapsed[i] += start.elapsed();
+
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    // tolerant bound, the unknown account path must not be much faster
+    assert!(elapsed[1] * 3 >= elapsed[0], "{:?}", elapsed);
+
+    Ok(())
+}
+
+#[test]
+pub async fn login_without_rbac() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::body::MessageBody;
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::responses::v1::auth::SlimAuthenticated;
+    use crate::testing::instance::user;
+
+    for embed in [true, false] {
+        let (service, db) = crate::service!(AuthConfig {
+            login_embed_rbac: embed,
+            ..AuthConfig::default()
+        });
+        let user = user(&db, &["READ_USER"]).await;
+        let request = TestRequest::default()
+            .method(Method::POST)
+            .uri("/login")
+            .set_json(&LoginRequest {
+                email_or_username: user.username.clone(),
+                password: "password".to_string(),
+            })
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body = response.into_body().boxed().try_into_bytes().unwrap();
+        let json = serde_json::from_slice::<serde_json::Value>(&body)?;
+
+        assert_eq!(json["user"].get("permissions").is_some(), embed);
+        assert_eq!(json["user"].get("roles").is_some(), embed);
+
+        let authenticated = serde_json::from_slice::<SlimAuthenticated>(&body)?;
+
+        assert_eq!(authenticated.user.id, user.id);
+
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", authenticated.token)))
+            .uri("/user")
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub async fn login_short_password_policy() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::config::{AuthConfig, PasswordPolicy};
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!(AuthConfig {
+        password_policy: PasswordPolicy {
+            min_length: 6,
+            ..PasswordPolicy::default()
+        },
+        ..AuthConfig::default()
+    });
+    let username = Uuid::new_v4().simple().to_string();
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&UserStoreRequest {
+            name: "John Doe".to_string(),
+            email: format!("{}@local", username),
+            username: username.clone(),
+            password: "s3cret".to_string(),
+            password_confirmation: "s3cret".to_string(),
+            profile_photo_id: None,
+            permissions: vec![],
+            roles: vec![],
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // a password allowed by the policy logs in even below the old fixed minimum
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/login")
+        .set_json(&LoginRequest {
+            email_or_username: username,
+            password: "s3cret".to_string(),
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    Ok(())
+}
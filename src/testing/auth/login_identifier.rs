@@ -0,0 +1,77 @@
+#[test]
+pub async fn login_identifier() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::config::{AuthConfig, LoginIdentifier};
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::testing::instance::user;
+
+    for (identifier, email, username) in [
+        (LoginIdentifier::Both, true, true),
+        (LoginIdentifier::Email, true, false),
+        (LoginIdentifier::Username, false, true),
+    ] {
+        let (service, db) = crate::service!(AuthConfig {
+            login_identifier: identifier,
+            ..AuthConfig::default()
+        });
+        let user = user(&db, &[]).await;
+
+        for (email_or_username, allowed) in [(&user.email, email), (&user.username, username)] {
+            let request = TestRequest::default()
+                .method(Method::POST)
+                .uri("/login")
+                .set_json(&LoginRequest {
+                    email_or_username: email_or_username.clone(),
+                    password: "password".to_string(),
+                })
+                .to_request();
+
+            let response = call_service(&service, request).await;
+
+            match allowed {
+                true => assert_eq!(response.status(), StatusCode::CREATED, "{:?}", identifier),
+                false => assert!(response.status().is_client_error(), "{:?}", identifier),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+pub async fn login_does_not_reveal_accounts() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::testing::instance::user;
+
+    let (service, db) = crate::service!();
+    let user = user(&db, &[]).await;
+    let mut responses = vec![];
+
+    for email_or_username in [user.username.clone(), Uuid::new_v4().simple().to_string()] {
+        let request = TestRequest::default()
+            .method(Method::POST)
+            .uri("/login")
+            .set_json(&LoginRequest {
+                email_or_username,
+                password: "wrong password".to_string(),
+            })
+            .to_request();
+
+        let response = call_service(&service, request).await;
+        let status = response.status();
+
+        responses.push((status, read_body(response).await));
+    }
+
+    assert_eq!(responses[0].0, StatusCode::UNAUTHORIZED);
+    assert_eq!(responses[0], responses[1]);
+
+    Ok(())
+}
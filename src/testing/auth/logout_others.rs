@@ -0,0 +1,77 @@
+#[test]
+pub async fn logout_others() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::base58;
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::users;
+
+    let (service, db) = crate::service!();
+    let id = Uuid::new_v4();
+    let user = users::Model {
+        id,
+        name: "logout others".to_string(),
+        email: format!("{}@local", id),
+        email_verified_at: None,
+        username: id.simple().to_string(),
+        password: Hash::make(id, "password").to_string(),
+        profile_photo_id: None,
+        created_at: now(),
+        updated_at: now(),
+        deleted_at: None,
+        last_login_at: None,
+    }
+    .store(&db, vec![], vec![])
+    .await?;
+
+    let current = base58::to_string(user.generate_token(&db, None).await?.id);
+    let others = vec![
+        base58::to_string(user.generate_token(&db, None).await?.id),
+        base58::to_string(user.generate_token(&db, None).await?.id),
+    ];
+
+    // warm up the cache for every session
+    for token in others.iter().chain([&current]) {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .uri("/user")
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", current)))
+        .method(Method::POST)
+        .uri("/v1/auth/logout-others")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", current)))
+        .uri("/user")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    for token in others {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .uri("/user")
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use actix_web::{HttpResponse, Responder};
+
+use crate::middlewares::v1::auth::internal::Auth;
+
+#[get("/guarded")]
+pub async fn guarded(auth: Auth) -> impl Responder {
+    HttpResponse::Ok().body(auth.user.username)
+}
+
+#[test]
+pub async fn malformed_token_skips_database() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::web::Data;
+    use actix_web::App;
+    use lighter_common::prelude::*;
+
+    use crate::middlewares::v1::auth::Authenticated;
+
+    // any query against a disconnected database fails with 500
+    let service = init_service(
+        App::new()
+            .app_data(Data::new(DatabaseConnection::Disconnected))
+            .app_data(Data::new(Authenticated::new()))
+            .service(guarded),
+    )
+    .await;
+
+    // not base58, then base58 of the wrong length
+    for token in ["0OIl", "abc"] {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .uri("/guarded")
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{}", token);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub async fn token_rejection() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::{base58, prelude::*};
+
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let user = user(&db, &[]).await;
+
+    for token in ["0OIl".to_string(), base58::to_string(Uuid::new_v4())] {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .uri("/user")
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{}", token);
+    }
+
+    for key in ["no-separator", "unknownprefx.secret"] {
+        let request = TestRequest::default()
+            .insert_header(("X-Api-Key", key))
+            .uri("/user")
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{}", key);
+    }
+
+    let request = authed_request(&db, &user).await.uri("/user").to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+#[test]
+pub async fn me() -> Result<(), lighter_common::prelude::Error> {
+    use std::collections::BTreeSet;
+
+    use actix_web::test::{call_service, read_body_json};
+    use lighter_common::prelude::*;
+
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::authed_request;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .permission("direct")
+        .permission("inherited")
+        .role("member", &["inherited"])
+        .user("me", &["member"], &["direct"])
+        .build(&db)
+        .await;
+    let user = world.user("me");
+    let request = authed_request(&db, user)
+        .await
+        .uri("/v1/auth/me")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let me: serde_json::Value = read_body_json(response).await;
+    let fields = me
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(|key| key.as_str())
+        .collect::<BTreeSet<_>>();
+
+    assert_eq!(
+        fields,
+        BTreeSet::from(["id", "permissions", "roles", "username"])
+    );
+    assert_eq!(me["id"], user.id.to_string());
+    assert_eq!(me["username"], user.username);
+
+    let permissions = me["permissions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|code| code.as_str().unwrap().to_string())
+        .collect::<BTreeSet<_>>();
+
+    assert_eq!(
+        permissions,
+        BTreeSet::from([
+            world.permission("direct").code.clone(),
+            world.permission("inherited").code.clone(),
+        ])
+    );
+    assert_eq!(me["roles"], serde_json::json!([world.role("member").code]));
+
+    Ok(())
+}
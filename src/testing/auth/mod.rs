@@ -0,0 +1,17 @@
+pub mod api_key;
+pub mod can;
+pub mod dormant;
+pub mod has_permission;
+pub mod last_login;
+pub mod login;
+pub mod login_identifier;
+pub mod logout_others;
+pub mod malformed_token;
+pub mod me;
+pub mod optional_auth;
+pub mod password_strength;
+pub mod revoke_token;
+pub mod session_mode;
+pub mod sessions;
+pub mod token_ttl;
+pub mod verify_email;
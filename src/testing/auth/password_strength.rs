@@ -0,0 +1,82 @@
+#[test]
+pub async fn password_strength_score() {
+    use crate::services::v1::auth::password_strength::strength;
+
+    for weak in ["password", "Password1!", "aaaaaaaaaaaa", "abc12345"] {
+        let strength = strength(weak, "");
+
+        assert_eq!(strength.score, 0, "{}", weak);
+        assert!(!strength.suggestions.is_empty(), "{}", weak);
+    }
+
+    let common = strength("qwerty123", "");
+
+    assert!(common
+        .warnings
+        .contains(&"This is a commonly used password.".to_string()));
+
+    let strong = strength("correct-Horse-battery-staple-92", "");
+
+    assert_eq!(strong.score, 4);
+    assert!(!strong
+        .suggestions
+        .iter()
+        .any(|suggestion| suggestion.contains("longer")));
+
+    // the username drags an otherwise strong password down
+    let personal = strength("correct-Horse-johndoe-staple-92", "JohnDoe");
+
+    assert!(personal.score <= 1);
+}
+
+#[test]
+pub async fn password_strength_endpoint() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::requests::v1::auth::PasswordStrengthRequest;
+    use crate::responses::v1::auth::PasswordStrength;
+
+    let (service, _) = crate::service!();
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/v1/auth/password/strength")
+        .set_json(&PasswordStrengthRequest {
+            password: "letmein".to_string(),
+            username: None,
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let strength: PasswordStrength = read_body_json(response).await;
+
+    assert_eq!(strength.score, 0);
+    assert!(!strength.warnings.is_empty());
+
+    Ok(())
+}
+
+#[test]
+pub async fn password_min_strength() {
+    use crate::config::PasswordPolicy;
+
+    let policy = PasswordPolicy {
+        min_strength: 3,
+        ..Default::default()
+    };
+
+    let errors = policy.validate("password123", "");
+
+    assert!(errors.contains(&"Password is too weak.".to_string()));
+    assert!(policy
+        .validate("correct-Horse-battery-staple-92", "")
+        .is_empty());
+    // disabled by default
+    assert!(PasswordPolicy::default()
+        .validate("password123", "")
+        .is_empty());
+}
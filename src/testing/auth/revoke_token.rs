@@ -0,0 +1,74 @@
+#[test]
+pub async fn revoke_token() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::call_service;
+    use lighter_common::prelude::*;
+    use sea_orm::EntityTrait;
+
+    use crate::entities::v1::tokens;
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let owner = user(&db, &[]).await;
+    let stranger = user(&db, &[]).await;
+    let admin = user(&db, &["REVOKE_TOKEN"]).await;
+    let revoke = |id: Uuid| format!("/v1/auth/token/{}", id);
+    let exists = |id: Uuid| {
+        let db = db.clone();
+
+        async move {
+            tokens::Entity::find_by_id(id)
+                .one(&db)
+                .await
+                .unwrap()
+                .is_some()
+        }
+    };
+
+    // someone else's token without permission
+    let token = owner.generate_token(&db, None).await?;
+    let request = authed_request(&db, &stranger)
+        .await
+        .method(Method::DELETE)
+        .uri(&revoke(token.session_id))
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(exists(token.id).await);
+
+    // own token
+    let request = authed_request(&db, &owner)
+        .await
+        .method(Method::DELETE)
+        .uri(&revoke(token.session_id))
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!exists(token.id).await);
+
+    // anyone's token with REVOKE_TOKEN
+    let token = owner.generate_token(&db, None).await?;
+    let request = authed_request(&db, &admin)
+        .await
+        .method(Method::DELETE)
+        .uri(&revoke(token.session_id))
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!exists(token.id).await);
+
+    // unknown token
+    let request = authed_request(&db, &admin)
+        .await
+        .method(Method::DELETE)
+        .uri(&revoke(Uuid::new_v4()))
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+#[test]
+pub async fn sliding() -> Result<(), lighter_common::prelude::Error> {
+    use std::time::Duration;
+
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::EntityTrait;
+
+    use crate::config::{AuthConfig, SessionMode};
+    use crate::entities::v1::tokens;
+    use crate::testing::instance::user;
+
+    async fn expired_at(db: &DatabaseConnection, id: Uuid) -> Option<NaiveDateTime> {
+        let token = tokens::Entity::find_by_id(id).one(db).await.unwrap();
+
+        token.unwrap().expired_at
+    }
+
+    let idle = Duration::from_secs(60 * 10);
+    let (service, db) = crate::service!(AuthConfig {
+        session_mode: SessionMode::Sliding,
+        session_idle_timeout: idle,
+        ..AuthConfig::default()
+    });
+    let user = user(&db, &[]).await;
+    let request = |id: Uuid| {
+        TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", base58::to_string(id))))
+            .uri("/user")
+            .to_request()
+    };
+
+    // activity carries the session well past its original expiry
+    let original = now() + Duration::from_secs(5);
+    let token = user.generate_token(&db, Some(original)).await?;
+    let response = call_service(&service, request(token.id)).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let extended = expired_at(&db, token.id).await.unwrap();
+
+    assert!(extended > original + Duration::from_secs(60));
+    assert!(extended <= now() + idle);
+
+    // throttled, an immediate request doesn't write again
+    let response = call_service(&service, request(token.id)).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(expired_at(&db, token.id).await, Some(extended));
+
+    // an idle session isn't revived
+    let stale = now() - Duration::from_secs(1);
+    let token = user.generate_token(&db, Some(stale)).await?;
+    let response = call_service(&service, request(token.id)).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    // databases may store the timestamp with less precision
+    let unchanged = expired_at(&db, token.id).await.unwrap() - stale;
+
+    assert!(unchanged.num_milliseconds().abs() < 1);
+
+    Ok(())
+}
+
+#[test]
+pub async fn absolute() -> Result<(), lighter_common::prelude::Error> {
+    use std::time::Duration;
+
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::EntityTrait;
+
+    use crate::config::{AuthConfig, SessionMode};
+    use crate::entities::v1::tokens;
+    use crate::testing::instance::user;
+
+    let config = AuthConfig::default();
+
+    assert_eq!(config.session_mode, SessionMode::Absolute);
+
+    let (service, db) = crate::service!(config);
+    let user = user(&db, &[]).await;
+    let original = now() + Duration::from_secs(5);
+    let token = user.generate_token(&db, Some(original)).await?;
+    let request = TestRequest::default()
+        .insert_header((
+            "Authorization",
+            format!("Bearer {}", base58::to_string(token.id)),
+        ))
+        .uri("/user")
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let token = tokens::Entity::find_by_id(token.id)
+        .one(&db)
+        .await?
+        .unwrap();
+
+    // databases may store the timestamp with less precision
+    let unchanged = token.expired_at.unwrap() - original;
+
+    assert!(unchanged.num_milliseconds().abs() < 1);
+
+    Ok(())
+}
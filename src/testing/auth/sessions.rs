@@ -0,0 +1,210 @@
+#[test]
+pub async fn sessions() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::body::MessageBody;
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::{base58, prelude::*};
+
+    use crate::entities::v1::users;
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::responses::v1::auth::{Authenticated, Session};
+
+    let (service, db) = crate::service!();
+    let id = Uuid::new_v4();
+    let user = users::Model {
+        id,
+        name: "sessions".to_string(),
+        email: format!("{}@local", id),
+        email_verified_at: Some(now()),
+        username: id.simple().to_string(),
+        password: Hash::make(id, "password").to_string(),
+        profile_photo_id: None,
+        created_at: now(),
+        updated_at: now(),
+        deleted_at: None,
+        last_login_at: None,
+    }
+    .store(&db, vec![], vec![])
+    .await?;
+
+    let mut tokens = vec![];
+
+    for agent in ["laptop", "phone"] {
+        let request = TestRequest::default()
+            .insert_header(("User-Agent", agent))
+            .method(Method::POST)
+            .uri("/login")
+            .set_json(&LoginRequest {
+                email_or_username: user.username.clone(),
+                password: "password".to_string(),
+            })
+            .to_request();
+
+        let response = call_service(&service, request).await;
+        let status = response.status();
+        let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+        assert_eq!(status, StatusCode::CREATED, "{:?}", body);
+
+        tokens.push(serde_json::from_slice::<Authenticated>(&body)?.token);
+    }
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", tokens[0])))
+        .uri("/v1/auth/sessions")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+    let status = response.status();
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+    assert_eq!(status, StatusCode::OK, "{:?}", body);
+
+    let sessions = serde_json::from_slice::<Vec<Session>>(&body)?;
+
+    assert_eq!(sessions.len(), 2);
+
+    // session ids are handles, never the bearer tokens themselves
+    for session in &sessions {
+        assert!(!tokens.contains(&session.id.to_string()));
+        assert!(!tokens.contains(&base58::to_string(session.id)));
+    }
+
+    let current = sessions.iter().find(|session| session.current).unwrap();
+    let other = sessions.iter().find(|session| !session.current).unwrap();
+
+    assert_eq!(current.user_agent.as_deref(), Some("laptop"));
+    assert_eq!(current.ip_address, None);
+    assert!(current.created_at.is_some());
+    assert!(current.last_used_at.is_some());
+
+    // revoke the other session
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", tokens[0])))
+        .method(Method::DELETE)
+        .uri(format!("/v1/auth/sessions/{}", other.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", tokens[1])))
+        .uri("/user")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", tokens[0])))
+        .uri("/v1/auth/sessions")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+    let sessions = serde_json::from_slice::<Vec<Session>>(&body)?;
+
+    assert_eq!(sessions.len(), 1);
+
+    // sessions of other users can not be revoked
+    let stranger = crate::testing::instance::user(&db, &[])
+        .await
+        .generate_token(&db, None)
+        .await?;
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", tokens[0])))
+        .method(Method::DELETE)
+        .uri(format!("/v1/auth/sessions/{}", stranger.session_id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // revoking the current session acts as logout
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", tokens[0])))
+        .method(Method::DELETE)
+        .uri(format!("/v1/auth/sessions/{}", current.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", tokens[0])))
+        .uri("/user")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[test]
+pub async fn sessions_touch_throttled() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::{base58, prelude::*};
+    use sea_orm::{ActiveModelTrait, EntityTrait, Set};
+
+    use crate::entities::v1::tokens;
+    use crate::middlewares::v1::auth::internal::TOUCH_THROTTLE;
+    use crate::testing::instance::user;
+
+    let (service, db) = crate::service!();
+    let token = user(&db, &[]).await.generate_token(&db, None).await?;
+    let stale = now() - TOUCH_THROTTLE * 2;
+    let mut model = tokens::ActiveModel::from(token.clone());
+
+    model.last_used_at = Set(Some(stale));
+    model.update(&db).await?;
+
+    let request = || {
+        TestRequest::default()
+            .insert_header((
+                "Authorization",
+                format!("Bearer {}", base58::to_string(token.id)),
+            ))
+            .uri("/user")
+            .to_request()
+    };
+    let last_used_at = || {
+        let db = db.clone();
+
+        async move {
+            tokens::Entity::find_by_id(token.id)
+                .one(&db)
+                .await
+                .unwrap()
+                .unwrap()
+                .last_used_at
+                .unwrap()
+        }
+    };
+
+    // a stale timestamp gets refreshed
+    let response = call_service(&service, request()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let touched = last_used_at().await;
+
+    assert!(touched > stale);
+
+    // requests within the throttle window don't write again
+    for _ in 0..3 {
+        let response = call_service(&service, request()).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    assert_eq!(last_used_at().await, touched);
+
+    Ok(())
+}
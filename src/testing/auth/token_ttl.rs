@@ -0,0 +1,84 @@
+#[test]
+pub async fn login_token_ttl() -> Result<(), lighter_common::prelude::Error> {
+    use std::time::Duration;
+
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::config::AuthConfig;
+    use crate::entities::v1::tokens;
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::testing::instance::user;
+
+    let config = AuthConfig {
+        access_token_ttl: Duration::from_secs(60 * 10),
+        ..AuthConfig::default()
+    };
+    let (service, db) = crate::service!(config.clone());
+    let user = user(&db, &[]).await;
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/login")
+        .set_json(&LoginRequest {
+            email_or_username: user.username.clone(),
+            password: "password".to_string(),
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let token = tokens::Entity::find()
+        .filter(tokens::Column::UserId.eq(user.id))
+        .one(&db)
+        .await?
+        .unwrap();
+    let expired_at = token.expired_at.unwrap();
+
+    assert!(expired_at > now() + Duration::from_secs(60 * 9));
+    assert!(expired_at <= now() + config.access_token_ttl);
+
+    Ok(())
+}
+
+#[test]
+pub async fn token_expiry() {
+    use std::time::Duration;
+
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+
+    let hour = Duration::from_secs(60 * 60);
+    let config = AuthConfig {
+        access_token_ttl: hour,
+        max_token_ttl: hour * 2,
+        allow_non_expiring_tokens: false,
+        ..AuthConfig::default()
+    };
+
+    // over long expiries are clamped
+    let expired_at = config.token_expiry(Some(now() + hour * 24)).unwrap();
+
+    assert!(expired_at <= now() + hour * 2);
+    assert!(expired_at > now() + hour);
+
+    // shorter ones are kept
+    let requested = now() + Duration::from_secs(60);
+
+    assert_eq!(config.token_expiry(Some(requested)), Some(requested));
+
+    // default ttl applies unless non-expiring tokens are opted in
+    assert!(config.token_expiry(None).unwrap() <= now() + hour);
+    assert_eq!(
+        AuthConfig {
+            allow_non_expiring_tokens: true,
+            ..config
+        }
+        .token_expiry(None),
+        None
+    );
+}
@@ -0,0 +1,229 @@
+#[test]
+pub async fn verify_email() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::body::MessageBody;
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::EntityTrait;
+
+    use crate::config::AuthConfig;
+    use crate::entities::v1::users;
+    use crate::services::v1::notification::{Notification, Notifications};
+    use crate::testing::instance::{token, Outbox};
+
+    let outbox = Outbox::new();
+    let (service, db) = crate::service!(AuthConfig::env(), Notifications::new(outbox.clone()));
+    let root = users::Entity::find_by_id(Uuid::from_u128(0))
+        .one(&db)
+        .await?
+        .unwrap();
+    let session = token(&db).await;
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", session)))
+        .method(Method::POST)
+        .uri("/v1/auth/send-verification")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+    let status = response.status();
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+    assert_eq!(status, StatusCode::ACCEPTED, "{:?}", body);
+
+    // the token only travels to the address being verified
+    let token = outbox.token(&root.email).unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body)?;
+
+    assert!(body.get("token").is_none());
+    assert!(!body.to_string().contains(&token));
+    assert!(matches!(
+        outbox.sent().as_slice(),
+        [Notification::VerifyEmail { user_id, .. }] if *user_id == root.id
+    ));
+
+    let request = TestRequest::default()
+        .uri(format!("/v1/auth/verify-email?token={}", token).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let user = users::Entity::find_by_id(Uuid::from_u128(0))
+        .one(&db)
+        .await?
+        .unwrap();
+
+    assert!(user.email_verified_at.is_some());
+
+    // the session cached before verifying sees the new state
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", session)))
+        .uri("/user")
+        .to_request();
+    let response = call_service(&service, request).await;
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body)?;
+
+    assert!(!body["user"]["emailVerifiedAt"].is_null());
+
+    // verifying an already verified email keeps the original timestamp
+    let verified = user.mark_email_verified(&db).await?;
+
+    assert_eq!(verified.email_verified_at, user.email_verified_at);
+
+    Ok(())
+}
+
+#[test]
+pub async fn verify_email_expired() -> Result<(), lighter_common::prelude::Error> {
+    use std::time::Duration;
+
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::EntityTrait;
+
+    use crate::config::AuthConfig;
+    use crate::entities::v1::users;
+    use crate::services::v1::notification::Notifications;
+    use crate::testing::instance::{token, Outbox};
+
+    let outbox = Outbox::new();
+    let (service, db) = crate::service!(
+        AuthConfig {
+            email_verification_lifetime: Duration::ZERO,
+            ..AuthConfig::default()
+        },
+        Notifications::new(outbox.clone())
+    );
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/auth/send-verification")
+        .to_request();
+
+    call_service(&service, request).await;
+
+    let user = users::Entity::find_by_id(Uuid::from_u128(0))
+        .one(&db)
+        .await?
+        .unwrap();
+    let token = outbox.token(&user.email).unwrap();
+    let request = TestRequest::default()
+        .uri(format!("/v1/auth/verify-email?token={}", token).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let user = users::Entity::find_by_id(Uuid::from_u128(0))
+        .one(&db)
+        .await?
+        .unwrap();
+
+    assert!(user.email_verified_at.is_none());
+
+    Ok(())
+}
+
+#[test]
+pub async fn login_requires_verified_email() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::EntityTrait;
+
+    use crate::config::AuthConfig;
+    use crate::entities::v1::users;
+    use crate::requests::v1::auth::LoginRequest;
+
+    let (service, db) = crate::service!(AuthConfig {
+        require_verified_email: true,
+        ..AuthConfig::default()
+    });
+    let payload = LoginRequest {
+        email_or_username: "root".to_string(),
+        password: "password".to_string(),
+    };
+
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/login")
+        .set_json(&payload)
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let user = users::Entity::find_by_id(Uuid::from_u128(0))
+        .one(&db)
+        .await?
+        .unwrap();
+
+    user.mark_email_verified(&db).await?;
+
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/login")
+        .set_json(&payload)
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    Ok(())
+}
+
+#[test]
+pub async fn verify_email_across_workers() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::App;
+    use lighter_common::prelude::*;
+    use sea_orm::EntityTrait;
+
+    use crate::entities::v1::users;
+    use crate::router::{configure, Shared};
+    use crate::services::v1::notification::Notifications;
+    use crate::testing::instance::{database, token, Outbox};
+
+    let db = database().await?;
+    let shared = Shared::new();
+    let outbox = Outbox::new();
+    let worker = || {
+        App::new()
+            .app_data(Data::new(db.clone()))
+            .configure(|app| configure(app, &shared))
+            .app_data(Data::new(Notifications::new(outbox.clone())))
+    };
+    let first = init_service(worker()).await;
+    let second = init_service(worker()).await;
+    let user = users::Entity::find_by_id(Uuid::from_u128(0))
+        .one(&db)
+        .await?
+        .unwrap();
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/auth/send-verification")
+        .to_request();
+    let response = call_service(&first, request).await;
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    // a token issued by one worker is known to the others
+    let token = outbox.token(&user.email).unwrap();
+    let request = TestRequest::default()
+        .uri(format!("/v1/auth/verify-email?token={}", token).as_str())
+        .to_request();
+    let response = call_service(&second, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+#[test]
+pub async fn clear() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::responses::v1::cache::{CacheCleared, CacheStats};
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let token = token(&db).await;
+    let stats = || {
+        TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .uri("/v1/admin/cache/stats")
+            .to_request()
+    };
+
+    let response = call_service(&service, stats()).await;
+    let before: CacheStats = read_body_json(response).await;
+
+    assert!(before.size >= 1);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .method(Method::POST)
+        .uri("/v1/admin/cache/clear")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let cleared: CacheCleared = read_body_json(response).await;
+
+    assert_eq!(cleared.removed, before.size);
+
+    // the session is reloaded from the database, so this request misses
+    let response = call_service(&service, stats()).await;
+    let after: CacheStats = read_body_json(response).await;
+
+    assert_eq!(after.size, 1);
+    assert_eq!(after.misses, before.misses + 1);
+
+    Ok(())
+}
+
+#[test]
+pub async fn clear_requires_permission() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::call_service;
+    use lighter_common::prelude::*;
+
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let user = user(&db, &[]).await;
+    let request = authed_request(&db, &user)
+        .await
+        .method(Method::POST)
+        .uri("/v1/admin/cache/clear")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
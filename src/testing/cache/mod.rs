@@ -0,0 +1,2 @@
+pub mod clear;
+pub mod stats;
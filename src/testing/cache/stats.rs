@@ -0,0 +1,60 @@
+#[test]
+pub async fn stats() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::responses::v1::cache::CacheStats;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let token = token(&db).await;
+
+    for _ in 0..3 {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .uri("/user")
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .uri("/v1/admin/cache/stats")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let stats: CacheStats = read_body_json(response).await;
+
+    assert!(stats.size >= 1);
+    assert!(stats.hits >= 3);
+    assert!(stats.misses >= 1);
+
+    Ok(())
+}
+
+#[test]
+pub async fn stats_requires_permission() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::call_service;
+    use lighter_common::prelude::*;
+
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let user = user(&db, &[]).await;
+    let request = authed_request(&db, &user)
+        .await
+        .uri("/v1/admin/cache/stats")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    Ok(())
+}
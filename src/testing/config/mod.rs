@@ -0,0 +1,3 @@
+pub mod password_policy;
+pub mod secret_files;
+pub mod validate;
@@ -0,0 +1,123 @@
+#[test]
+pub async fn password_policy_length() {
+    use crate::config::PasswordPolicy;
+
+    let policy = PasswordPolicy {
+        min_length: 10,
+        max_length: 12,
+        ..Default::default()
+    };
+
+    assert_eq!(policy.validate("short", "").len(), 1);
+    assert_eq!(policy.validate("long enough", "").len(), 0);
+    assert_eq!(policy.validate("way too long password", "").len(), 1);
+    // length is counted in characters, not bytes
+    assert_eq!(policy.validate("ääääääääää", "").len(), 0);
+}
+
+#[test]
+pub async fn password_policy_character_classes() {
+    use crate::config::PasswordPolicy;
+
+    let relaxed = PasswordPolicy::default();
+
+    assert!(relaxed.validate("password", "").is_empty());
+
+    for (policy, failing, passing) in [
+        (
+            PasswordPolicy {
+                require_uppercase: true,
+                ..Default::default()
+            },
+            "password",
+            "Password",
+        ),
+        (
+            PasswordPolicy {
+                require_lowercase: true,
+                ..Default::default()
+            },
+            "PASSWORD",
+            "PASSWORd",
+        ),
+        (
+            PasswordPolicy {
+                require_digit: true,
+                ..Default::default()
+            },
+            "password",
+            "passw0rd",
+        ),
+        (
+            PasswordPolicy {
+                require_symbol: true,
+                ..Default::default()
+            },
+            "password",
+            "passw@rd",
+        ),
+    ] {
+        assert_eq!(policy.validate(failing, "").len(), 1, "{}", failing);
+        assert!(policy.validate(passing, "").is_empty(), "{}", passing);
+    }
+}
+
+#[test]
+pub async fn password_policy_disallowed() {
+    use crate::config::PasswordPolicy;
+
+    let policy = PasswordPolicy {
+        disallow_username: true,
+        disallowed: vec!["qwerty".to_string()],
+        ..Default::default()
+    };
+
+    assert_eq!(policy.validate("my-JohnDoe-secret", "johndoe").len(), 1);
+    assert_eq!(policy.validate("QWERTY123", "johndoe").len(), 1);
+    assert!(policy.validate("something else", "johndoe").is_empty());
+
+    let policy = PasswordPolicy::default();
+
+    assert!(policy.validate("my-johndoe-secret", "johndoe").is_empty());
+}
+
+#[test]
+pub async fn store_rejects_password_with_username() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::config::{AuthConfig, PasswordPolicy};
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!(AuthConfig {
+        password_policy: PasswordPolicy {
+            disallow_username: true,
+            ..Default::default()
+        },
+        ..AuthConfig::default()
+    });
+    let username = Uuid::new_v4().simple().to_string();
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&UserStoreRequest {
+            name: "policy".to_string(),
+            email: format!("{}@local", username),
+            username: username.clone(),
+            password: format!("{}!", username),
+            password_confirmation: format!("{}!", username),
+            profile_photo_id: None,
+            permissions: vec![],
+            roles: vec![],
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    Ok(())
+}
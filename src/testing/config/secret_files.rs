@@ -0,0 +1,33 @@
+#[test]
+pub async fn secret_files() {
+    use std::{env, fs};
+
+    use lighter_common::prelude::Uuid;
+
+    use crate::config::{load_secret_files, var};
+
+    // unique names, tests share the process environment
+    let suffix = Uuid::new_v4().simple().to_string().to_uppercase();
+    let key = format!("LIGHTER_TEST_SECRET_{}", suffix);
+    let path = env::temp_dir().join(format!("lighter-test-secret-{}", suffix));
+
+    fs::write(&path, "from file\n").unwrap();
+
+    assert!(var(&key).is_err());
+
+    env::set_var(&key, "inline");
+
+    assert_eq!(var(&key).unwrap(), "inline");
+
+    // the file wins over the inline value, without its trailing newline
+    env::set_var(format!("{}_FILE", key), path.to_str().unwrap());
+
+    assert_eq!(var(&key).unwrap(), "from file");
+
+    // exported for settings read outside this crate
+    load_secret_files(&[&key]);
+
+    assert_eq!(env::var(&key).unwrap(), "from file");
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,60 @@
+#[test]
+pub async fn valid_config() {
+    use crate::config::AuthConfig;
+
+    assert!(AuthConfig::default().validate().is_ok());
+}
+
+#[test]
+pub async fn invalid_combinations() {
+    use std::time::Duration;
+
+    use crate::config::{AuthConfig, PasswordPolicy, SessionMode};
+
+    let cases = [
+        AuthConfig {
+            password_policy: PasswordPolicy {
+                min_length: 20,
+                max_length: 10,
+                ..PasswordPolicy::default()
+            },
+            ..AuthConfig::default()
+        },
+        AuthConfig {
+            access_token_ttl: Duration::from_secs(60 * 60 * 24 * 60),
+            ..AuthConfig::default()
+        },
+        AuthConfig {
+            email_verification_lifetime: Duration::ZERO,
+            ..AuthConfig::default()
+        },
+        AuthConfig {
+            session_mode: SessionMode::Sliding,
+            session_idle_timeout: Duration::ZERO,
+            ..AuthConfig::default()
+        },
+    ];
+
+    for config in cases {
+        let errors = config.validate().unwrap_err();
+
+        assert_eq!(errors.len(), 1, "{:?}", errors);
+    }
+
+    // every problem is reported at once
+    let config = AuthConfig {
+        password_policy: PasswordPolicy {
+            min_length: 20,
+            max_length: 10,
+            ..PasswordPolicy::default()
+        },
+        max_token_ttl: Duration::ZERO,
+        ..AuthConfig::default()
+    };
+    let errors = config.validate().unwrap_err();
+
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("AUTH_PASSWORD_MIN_LENGTH")));
+    assert!(errors.iter().any(|e| e.contains("AUTH_MAX_TOKEN_TTL")));
+}
@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+
+use lighter_common::prelude::*;
+
+use crate::entities::v1::{permissions, roles, users};
+
+/// Declarative permissions, roles and users for a test
+///
+/// Codes and usernames get a random suffix so worlds never collide in the
+/// shared test database, the built handles are looked up by declared name
+#[derive(Clone, Default)]
+pub struct World {
+    permissions: Vec<String>,
+    roles: Vec<(String, Option<String>, Vec<String>)>,
+    users: Vec<(String, Vec<String>, Vec<String>)>,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn permission(mut self, name: &str) -> Self {
+        self.permissions.push(name.to_string());
+        self
+    }
+
+    /// Role granting the given declared permissions
+    pub fn role(self, name: &str, permissions: &[&str]) -> Self {
+        self.child_role(name, None, permissions)
+    }
+
+    /// Role inheriting from a previously declared role
+    pub fn child_role(mut self, name: &str, parent: Option<&str>, permissions: &[&str]) -> Self {
+        self.roles.push((
+            name.to_string(),
+            parent.map(|parent| parent.to_string()),
+            permissions.iter().map(|p| p.to_string()).collect(),
+        ));
+        self
+    }
+
+    /// User, password is `password`, with the given declared roles and direct permissions
+    pub fn user(mut self, name: &str, roles: &[&str], permissions: &[&str]) -> Self {
+        self.users.push((
+            name.to_string(),
+            roles.iter().map(|r| r.to_string()).collect(),
+            permissions.iter().map(|p| p.to_string()).collect(),
+        ));
+        self
+    }
+
+    pub async fn build(self, db: &DatabaseConnection) -> Built {
+        let suffix = Uuid::new_v4().simple().to_string();
+        let mut built = Built::default();
+
+        for name in self.permissions {
+            let permission = permissions::Model {
+                id: Uuid::new_v4(),
+                code: format!("{}_{}", name, suffix).to_uppercase(),
+                name: format!("{} {}", name, suffix),
+            }
+            .store(db)
+            .await
+            .unwrap();
+
+            built.permissions.insert(name, permission);
+        }
+
+        for (name, parent, permissions) in self.roles {
+            let role = roles::Model {
+                id: Uuid::new_v4(),
+                code: format!("{}_{}", name, suffix).to_uppercase(),
+                name: format!("{} {}", name, suffix),
+                parent_id: parent.map(|parent| built.role(&parent).id),
+            }
+            .store(db, built.permissions_of(&permissions))
+            .await
+            .unwrap();
+
+            built.roles.insert(name, role);
+        }
+
+        for (name, roles, permissions) in self.users {
+            let id = Uuid::new_v4();
+            let roles = roles.iter().map(|role| built.role(role).clone()).collect();
+            let user = users::Model {
+                id,
+                name: name.clone(),
+                email: format!("{}@local", id),
+                email_verified_at: None,
+                username: id.simple().to_string(),
+                password: Hash::make(id, "password").to_string(),
+                profile_photo_id: None,
+                created_at: now(),
+                updated_at: now(),
+                deleted_at: None,
+                last_login_at: None,
+            }
+            .store(db, built.permissions_of(&permissions), roles)
+            .await
+            .unwrap();
+
+            built.users.insert(name, user);
+        }
+
+        built
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Built {
+    pub permissions: BTreeMap<String, permissions::Model>,
+    pub roles: BTreeMap<String, roles::Model>,
+    pub users: BTreeMap<String, users::Model>,
+}
+
+impl Built {
+    pub fn permission(&self, name: &str) -> &permissions::Model {
+        self.permissions
+            .get(name)
+            .unwrap_or_else(|| panic!("permission {} is not declared", name))
+    }
+
+    pub fn role(&self, name: &str) -> &roles::Model {
+        self.roles
+            .get(name)
+            .unwrap_or_else(|| panic!("role {} is not declared", name))
+    }
+
+    pub fn user(&self, name: &str) -> &users::Model {
+        self.users
+            .get(name)
+            .unwrap_or_else(|| panic!("user {} is not declared", name))
+    }
+
+    fn permissions_of(&self, names: &[String]) -> Vec<permissions::Model> {
+        names
+            .iter()
+            .map(|name| self.permission(name).clone())
+            .collect()
+    }
+}
+
+#[test]
+async fn world() {
+    use std::collections::BTreeSet;
+
+    let db = crate::testing::instance::database().await.unwrap();
+    let world = World::new()
+        .permission("read")
+        .permission("write")
+        .permission("audit")
+        .role("reader", &["read"])
+        .child_role("editor", Some("reader"), &["write"])
+        .user("alice", &["editor"], &[])
+        .user("bob", &["reader"], &["audit"])
+        .user("carol", &[], &[])
+        .build(&db)
+        .await;
+
+    for (user, expected) in [
+        ("alice", vec!["read", "write"]),
+        ("bob", vec!["audit", "read"]),
+        ("carol", vec![]),
+    ] {
+        let resolved = world
+            .user(user)
+            .permissions(&db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|permission| permission.code)
+            .collect::<BTreeSet<_>>();
+        let expected = expected
+            .into_iter()
+            .map(|name| world.permission(name).code.clone())
+            .collect::<BTreeSet<_>>();
+
+        assert_eq!(resolved, expected, "{}", user);
+    }
+}
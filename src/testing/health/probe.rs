@@ -0,0 +1,42 @@
+#[test]
+pub async fn health() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    let (service, _) = crate::service!();
+
+    for uri in ["/health/live", "/health/ready"] {
+        let request = TestRequest::default().uri(uri).to_request();
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub async fn health_database_unreachable() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::App;
+    use lighter_common::prelude::*;
+
+    let service = init_service(
+        App::new()
+            .app_data(Data::new(DatabaseConnection::Disconnected))
+            .configure(crate::router::route),
+    )
+    .await;
+
+    let request = TestRequest::default().uri("/health/live").to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::default().uri("/health/ready").to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    Ok(())
+}
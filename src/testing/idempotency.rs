@@ -0,0 +1,175 @@
+#[test]
+pub async fn idempotency() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+
+    use crate::entities::v1::permissions;
+    use crate::requests::v1::permission::PermissionRequest;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let token = token(&db).await;
+    let key = Uuid::new_v4().to_string();
+    let name = format!("idempotent {}", Uuid::new_v4().simple());
+    let request = |name: &str| {
+        TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .insert_header(("Idempotency-Key", key.as_str()))
+            .method(Method::POST)
+            .uri("/v1/permission")
+            .set_json(&PermissionRequest {
+                name: name.to_string(),
+            })
+            .to_request()
+    };
+
+    let first = call_service(&service, request(&name)).await;
+    let status = first.status();
+    let first = read_body(first).await;
+
+    assert!(status.is_success(), "{:?}", first);
+
+    // the retry replays the first response instead of failing on the duplicate code
+    let second = call_service(&service, request(&name)).await;
+
+    assert_eq!(second.status(), status);
+    assert_eq!(read_body(second).await, first);
+
+    let count = permissions::Entity::find()
+        .filter(permissions::Column::Name.eq(name.clone()))
+        .count(&db)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    let conflict = call_service(&service, request("something else")).await;
+
+    assert_eq!(conflict.status(), StatusCode::CONFLICT);
+
+    Ok(())
+}
+
+#[test]
+pub async fn idempotency_concurrent() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use actix_web::rt::{spawn, time::sleep};
+    use actix_web::test::TestRequest;
+    use lighter_common::prelude::*;
+
+    use crate::middlewares::v1::idempotency::Idempotency;
+
+    let idempotency = Idempotency::new();
+    let runs = Rc::new(Cell::new(0));
+    let key = Uuid::new_v4().to_string();
+    let request = {
+        let key = key.clone();
+
+        move || {
+            TestRequest::post()
+                .uri("/v1/permission")
+                .insert_header(("Idempotency-Key", key.as_str()))
+                .to_http_request()
+        }
+    };
+    let handler = |runs: Rc<Cell<u32>>| async move {
+        runs.set(runs.get() + 1);
+        sleep(Duration::from_millis(100)).await;
+
+        HttpResponse::Created().body("created")
+    };
+
+    let first = spawn({
+        let idempotency = idempotency.clone();
+        let runs = runs.clone();
+        let request = request.clone();
+
+        async move {
+            idempotency
+                .run(&request(), "{}".to_string(), handler(runs))
+                .await
+                .status()
+        }
+    });
+
+    // let the first request reserve the key
+    sleep(Duration::from_millis(10)).await;
+
+    // a duplicate arriving meanwhile doesn't run the handler again
+    let second = idempotency
+        .run(&request(), "{}".to_string(), handler(runs.clone()))
+        .await;
+
+    assert_eq!(second.status(), StatusCode::CONFLICT);
+    assert_eq!(first.await.unwrap(), StatusCode::CREATED);
+    assert_eq!(runs.get(), 1);
+
+    // once stored, retries replay it
+    let third = idempotency
+        .run(&request(), "{}".to_string(), handler(runs.clone()))
+        .await;
+
+    assert_eq!(third.status(), StatusCode::CREATED);
+    assert_eq!(runs.get(), 1);
+}
+
+#[test]
+pub async fn idempotency_across_workers() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, init_service, read_body, TestRequest};
+    use actix_web::App;
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+
+    use crate::entities::v1::permissions;
+    use crate::requests::v1::permission::PermissionRequest;
+    use crate::router::{configure, Shared};
+    use crate::testing::instance::{database, token};
+
+    let db = database().await?;
+    let shared = Shared::new();
+    let worker = || {
+        App::new()
+            .app_data(Data::new(db.clone()))
+            .configure(|app| configure(app, &shared))
+    };
+    let first = init_service(worker()).await;
+    let second = init_service(worker()).await;
+    let token = token(&db).await;
+    let key = Uuid::new_v4().to_string();
+    let name = format!("idempotent {}", Uuid::new_v4().simple());
+    let request = || {
+        TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .insert_header(("Idempotency-Key", key.as_str()))
+            .method(Method::POST)
+            .uri("/v1/permission")
+            .set_json(&PermissionRequest { name: name.clone() })
+            .to_request()
+    };
+
+    let response = call_service(&first, request()).await;
+    let status = response.status();
+    let body = read_body(response).await;
+
+    assert!(status.is_success(), "{:?}", body);
+
+    // the retry lands on another worker and still replays
+    let response = call_service(&second, request()).await;
+
+    assert_eq!(response.status(), status);
+    assert_eq!(read_body(response).await, body);
+
+    let count = permissions::Entity::find()
+        .filter(permissions::Column::Name.eq(name.clone()))
+        .count(&db)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+#[test]
+pub async fn impersonate() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::entities::v1::audit_logs;
+    use crate::responses::v1::auth::{Authenticated, Me};
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let support = user(&db, &["IMPERSONATE"]).await;
+    let target = user(&db, &["READ_USER"]).await;
+    let request = authed_request(&db, &support)
+        .await
+        .method(Method::POST)
+        .uri(format!("/v1/admin/impersonate/{}", target.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let authenticated: Authenticated = read_body_json(response).await;
+
+    assert_eq!(authenticated.user.id, target.id);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", authenticated.token)))
+        .uri("/v1/auth/me")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let me: Me = read_body_json(response).await;
+
+    assert_eq!(me.id, target.id);
+    assert_eq!(me.impersonated_by, Some(support.id));
+    assert!(me.permissions.contains(&"READ_USER".to_string()));
+
+    // an impersonated session can't impersonate further
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", authenticated.token)))
+        .method(Method::POST)
+        .uri(format!("/v1/admin/impersonate/{}", support.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let logs = audit_logs::Entity::find()
+        .filter(audit_logs::Column::TargetId.eq(target.id))
+        .filter(audit_logs::Column::Action.eq("impersonate"))
+        .all(&db)
+        .await?;
+
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].actor_user_id, Some(support.id));
+
+    // regular sessions aren't flagged
+    let request = authed_request(&db, &target)
+        .await
+        .uri("/v1/auth/me")
+        .to_request();
+
+    let me: Me = read_body_json(call_service(&service, request).await).await;
+
+    assert_eq!(me.impersonated_by, None);
+
+    Ok(())
+}
+
+#[test]
+pub async fn impersonate_rejected() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::call_service;
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::entities::v1::audit_logs;
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let support = user(&db, &["IMPERSONATE"]).await;
+    let protected = user(&db, &["IMPERSONATE"]).await;
+    let target = user(&db, &[]).await;
+    let bystander = user(&db, &[]).await;
+
+    for (actor, target) in [(&support, &protected), (&bystander, &target)] {
+        let request = authed_request(&db, actor)
+            .await
+            .method(Method::POST)
+            .uri(format!("/v1/admin/impersonate/{}", target.id).as_str())
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let logs = audit_logs::Entity::find()
+            .filter(audit_logs::Column::TargetId.eq(target.id))
+            .filter(audit_logs::Column::Action.eq("impersonate"))
+            .all(&db)
+            .await?;
+
+        assert!(logs.is_empty());
+    }
+
+    Ok(())
+}
+
+#[test]
+pub async fn impersonation_logout() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::base58;
+    use lighter_common::prelude::*;
+
+    use crate::responses::v1::auth::Authenticated;
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let support = user(&db, &["IMPERSONATE"]).await;
+    let target = user(&db, &[]).await;
+    let session = base58::to_string(target.generate_token(&db, None).await?.id);
+    let request = authed_request(&db, &support)
+        .await
+        .method(Method::POST)
+        .uri(format!("/v1/admin/impersonate/{}", target.id).as_str())
+        .to_request();
+
+    let authenticated: Authenticated = read_body_json(call_service(&service, request).await).await;
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", authenticated.token)))
+        .method(Method::DELETE)
+        .uri("/logout")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", authenticated.token)))
+        .uri("/v1/auth/me")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    // the target's own session outlives the impersonation token
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", session)))
+        .uri("/v1/auth/me")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
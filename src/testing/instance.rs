@@ -1,8 +1,12 @@
+use std::sync::{Arc, Mutex};
+
+use actix_web::test::TestRequest;
 use lighter_auth_migration::MigratorTrait;
 use lighter_common::{base58, prelude::*};
 use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, EntityTrait, QueryFilter};
 
-use crate::entities::v1::tokens;
+use crate::entities::v1::{permissions, tokens, users};
+use crate::services::v1::notification::{Notification, Notifier};
 
 pub async fn token(db: &DatabaseConnection) -> String {
     let user_id = Uuid::from_u128(0);
@@ -20,6 +24,12 @@ pub async fn token(db: &DatabaseConnection) -> String {
                 id,
                 user_id,
                 expired_at: None,
+                user_agent: None,
+                ip_address: None,
+                created_at: Some(now()),
+                last_used_at: None,
+                impersonated_by: None,
+                session_id: Uuid::new_v4(),
             };
 
             let model = tokens::ActiveModel::from(model);
@@ -30,6 +40,88 @@ pub async fn token(db: &DatabaseConnection) -> String {
     })
 }
 
+/// Store a fresh user, password is `password`, with the given permission codes granted directly
+pub async fn user(db: &DatabaseConnection, permissions: &[&str]) -> users::Model {
+    let id = Uuid::new_v4();
+    let user = users::Model {
+        id,
+        name: id.simple().to_string(),
+        email: format!("{}@local", id),
+        email_verified_at: None,
+        username: id.simple().to_string(),
+        password: Hash::make(id, "password").to_string(),
+        profile_photo_id: None,
+        created_at: now(),
+        updated_at: now(),
+        deleted_at: None,
+        last_login_at: None,
+    }
+    .store(db, vec![], vec![])
+    .await
+    .unwrap();
+
+    if !permissions.is_empty() {
+        let ids = permissions::Entity::find()
+            .filter(permissions::Column::Code.is_in(permissions.iter().copied()))
+            .all(db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|permission| permission.id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids.len(), permissions.len(), "unknown permission code");
+
+        user.assign_permissions(db, ids).await.unwrap();
+    }
+
+    user
+}
+
+/// Request authorized with a new session of `user`
+pub async fn authed_request(db: &DatabaseConnection, user: &users::Model) -> TestRequest {
+    let token = user.generate_token(db, None).await.unwrap();
+
+    TestRequest::default().insert_header((
+        "Authorization",
+        format!("Bearer {}", base58::to_string(token.id)),
+    ))
+}
+
+/// Notifier keeping every notification, pass `Notifications::new(outbox.clone())`
+/// to `service!` and read the delivered tokens back
+#[derive(Clone, Default)]
+pub struct Outbox {
+    sent: Arc<Mutex<Vec<Notification>>>,
+}
+
+impl Outbox {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Token of the last notification delivered to `email`
+    pub fn token(&self, email: &str) -> Option<String> {
+        self.sent
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|notification| notification.email() == email)
+            .map(|notification| notification.token().to_string())
+    }
+
+    pub fn sent(&self) -> Vec<Notification> {
+        self.sent.lock().unwrap().clone()
+    }
+}
+
+impl Notifier for Outbox {
+    fn notify(&self, notification: Notification) {
+        self.sent.lock().unwrap().push(notification);
+    }
+}
+
 pub async fn database() -> Result<DatabaseConnection, DbErr> {
     let db = database::env().await?;
 
@@ -45,6 +137,44 @@ async fn database_connected() {
     assert_eq!(db.ping().await, Ok(()));
 }
 
+#[test]
+async fn authed_request_carries_permissions() {
+    use actix_web::test::{call_service, read_body_json};
+
+    let (service, db) = crate::service!();
+    let user = user(&db, &["READ_USER"]).await;
+    let request = authed_request(&db, &user).await.uri("/user").to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let auth: serde_json::Value = read_body_json(response).await;
+    let codes = auth["permissions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|permission| permission["code"].as_str().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(auth["user"]["id"], user.id.to_string());
+    assert_eq!(codes, vec!["READ_USER"]);
+}
+
+#[test]
+async fn authed_request_without_permissions() {
+    use actix_web::test::call_service;
+
+    let (service, db) = crate::service!();
+    let user = user(&db, &[]).await;
+    let request = authed_request(&db, &user)
+        .await
+        .uri("/v1/admin/cache/stats")
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
 #[macro_export]
 macro_rules! app {
     () => {};
@@ -53,13 +183,17 @@ macro_rules! app {
 #[macro_export]
 macro_rules! service {
     () => {{
+        crate::service!(crate::config::AuthConfig::env())
+    }};
+    ($config:expr $(, $data:expr)*) => {{
         let db = crate::testing::instance::database().await.unwrap();
         let app = ::actix_web::App::new()
             .app_data(::actix_web::web::Data::new(db.clone()))
-            .app_data(::actix_web::web::Data::new(
-                crate::middlewares::v1::auth::Authenticated::new(),
-            ))
-            .configure(crate::router::route);
+            .configure(|app| {
+                crate::router::configure(app, &crate::router::Shared::new())
+            })
+            .app_data(::actix_web::web::Data::new($config))
+            $(.app_data(::actix_web::web::Data::new($data)))*;
 
         let service = ::actix_web::test::init_service(app).await;
 
@@ -1,2 +1,16 @@
-pub mod user;
+pub mod audit;
+pub mod auth;
+pub mod cache;
+pub mod config;
+pub mod fixtures;
+pub mod health;
+pub mod idempotency;
+pub mod impersonation;
 pub mod instance;
+pub mod openapi;
+pub mod permission;
+pub mod request_id;
+pub mod requests;
+pub mod role;
+pub mod user;
+pub mod version;
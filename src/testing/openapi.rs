@@ -0,0 +1,39 @@
+#[test]
+pub async fn openapi() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    let (service, _) = crate::service!();
+    let request = TestRequest::default().uri("/openapi.json").to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let spec: serde_json::Value = read_body_json(response).await;
+
+    assert!(spec["paths"]["/login"]["post"].is_object());
+    assert!(spec["paths"]["/v1/user"]["post"].is_object());
+    assert!(spec["components"]["securitySchemes"]["token"].is_object());
+    assert_eq!(
+        spec["components"]["securitySchemes"]["api_key"]["name"],
+        "X-Api-Key"
+    );
+
+    for uri in ["/docs", "/docs/"] {
+        let request = TestRequest::default().uri(uri).to_request();
+        let response = call_service(&service, request).await;
+
+        assert!(!response.status().is_client_error(), "{}", uri);
+    }
+
+    // the spec is only served once, the old locations point to the new ones
+    for (uri, location) in [("/doc", "/docs/"), ("/api.json", "/openapi.json")] {
+        let request = TestRequest::default().uri(uri).to_request();
+        let response = call_service(&service, request).await;
+
+        assert!(response.status().is_redirection(), "{}", uri);
+        assert_eq!(response.headers().get("location").unwrap(), location);
+    }
+
+    Ok(())
+}
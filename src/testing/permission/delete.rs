@@ -0,0 +1,49 @@
+#[test]
+pub async fn delete_cleans_up_links() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+
+    use crate::entities::v1::{permission_role, permission_user};
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .permission("doomed")
+        .role("holder", &["doomed"])
+        .user("direct", &[], &["doomed"])
+        .user("inherited", &["holder"], &[])
+        .build(&db)
+        .await;
+    let permission = world.permission("doomed");
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::DELETE)
+        .uri(format!("/v1/permission/{}", permission.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let permission_roles = permission_role::Entity::find()
+        .filter(permission_role::Column::PermissionId.eq(permission.id))
+        .count(&db)
+        .await?;
+    let permission_users = permission_user::Entity::find()
+        .filter(permission_user::Column::PermissionId.eq(permission.id))
+        .count(&db)
+        .await?;
+
+    assert_eq!(permission_roles, 0);
+    assert_eq!(permission_users, 0);
+
+    for user in ["direct", "inherited"] {
+        assert!(world.user(user).permissions(&db).await?.is_empty());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,3 @@
+pub mod delete;
+pub mod pages;
+pub mod pagination;
@@ -0,0 +1,65 @@
+#[test]
+pub async fn pagination_metadata() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::responses::v1::permission::PermissionPaginationResponse;
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let names = (0..30).map(|i| format!("page{:02}", i)).collect::<Vec<_>>();
+    let world = names
+        .iter()
+        .fold(World::new(), |world, name| world.permission(name))
+        .build(&db)
+        .await;
+    let suffix = world.permission("page00").name.replace("page00 ", "");
+    let token = token(&db).await;
+    let mut seen = vec![];
+    let mut page = 1;
+
+    loop {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .uri(&format!("/v1/permission?search={}&page={}", suffix, page))
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response: PermissionPaginationResponse = read_body_json(response).await;
+
+        // the count honours the search like the page query does
+        assert_eq!(response.total, 30);
+        assert_eq!(response.page, page);
+
+        let limit = match seen.first() {
+            Some((_, len)) => *len,
+            None => response.data.len() as u64,
+        };
+
+        assert_eq!(response.pages, 30_u64.div_ceil(limit));
+
+        seen.push((page, response.data.len() as u64));
+
+        if page == response.pages {
+            // first, middle and last pages are full except for the remainder
+            assert_eq!(response.data.len() as u64, 30 - limit * (page - 1));
+            break;
+        }
+
+        assert_eq!(response.data.len() as u64, limit);
+
+        page += 1;
+    }
+
+    assert_eq!(seen.iter().map(|(_, len)| len).sum::<u64>(), 30);
+
+    assert_eq!(crate::services::v1::pages(0, 10), 1);
+    assert_eq!(crate::services::v1::pages(20, 10), 2);
+    assert_eq!(crate::services::v1::pages(21, 10), 3);
+
+    Ok(())
+}
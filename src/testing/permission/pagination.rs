@@ -0,0 +1,69 @@
+#[test]
+pub async fn permission_sorting() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::responses::v1::permission::{
+        PermissionPaginationOrder, PermissionPaginationResponse, PermissionPaginationSort,
+    };
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .permission("bravo")
+        .permission("alpha")
+        .permission("charlie")
+        .build(&db)
+        .await;
+    let suffix = world.permission("alpha").name.replace("alpha ", "");
+    let value = |value: serde_json::Value| value.as_str().unwrap().to_string();
+    let order = value(serde_json::to_value(PermissionPaginationOrder::Name)?);
+
+    for (sort, expected) in [
+        (PermissionPaginationSort::Asc, ["alpha", "bravo", "charlie"]),
+        (
+            PermissionPaginationSort::Desc,
+            ["charlie", "bravo", "alpha"],
+        ),
+    ] {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+            .uri(&format!(
+                "/v1/permission?search={}&order={}&sort={}",
+                suffix,
+                order,
+                value(serde_json::to_value(sort)?),
+            ))
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let page: PermissionPaginationResponse = read_body_json(response).await;
+        let names = page
+            .data
+            .into_iter()
+            .map(|permission| permission.name)
+            .collect::<Vec<_>>();
+        let expected = expected
+            .iter()
+            .map(|name| world.permission(name).name.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, expected);
+    }
+
+    // only fields annotated with #[order] can be sorted on
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .uri("/v1/permission?order=id")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    Ok(())
+}
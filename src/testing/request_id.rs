@@ -0,0 +1,30 @@
+#[test]
+pub async fn request_id() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    let (service, _) = crate::service!();
+    let request = TestRequest::default()
+        .insert_header(("X-Request-Id", "trace-me"))
+        .uri("/health/live")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.headers().get("X-Request-Id").unwrap(), "trace-me");
+
+    let request = TestRequest::default().uri("/health/live").to_request();
+    let response = call_service(&service, request).await;
+    let id = response.headers().get("X-Request-Id").unwrap();
+
+    assert!(Uuid::parse_str(id.to_str().unwrap()).is_ok());
+
+    // not found responses are tagged too
+    let request = TestRequest::default().uri("/nowhere").to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.headers().contains_key("X-Request-Id"));
+
+    Ok(())
+}
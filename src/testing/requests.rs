@@ -0,0 +1,79 @@
+#[test]
+pub async fn uuid_list_duplicates() {
+    use lighter_common::prelude::Uuid;
+
+    use crate::requests::v1::UuidListRules;
+
+    let id = Uuid::new_v4();
+    let other = Uuid::new_v4();
+    let unique = UuidListRules {
+        item: "permission",
+        required: false,
+        max: 2,
+        unique: true,
+    };
+
+    assert!(unique.validate(&[id, other]).is_empty());
+    assert_eq!(
+        unique.validate(&[id, other, id]),
+        vec![format!("Permission {} is repeated.", id)]
+    );
+
+    // duplicates count once when they aren't rejected
+    let lenient = UuidListRules {
+        unique: false,
+        ..unique
+    };
+
+    assert!(lenient.validate(&[id, other, id, other]).is_empty());
+}
+
+#[test]
+pub async fn uuid_list_empty() {
+    use lighter_common::prelude::Uuid;
+
+    use crate::requests::v1::UuidListRules;
+
+    let optional = UuidListRules {
+        item: "role",
+        required: false,
+        max: 2,
+        unique: true,
+    };
+    let required = UuidListRules {
+        required: true,
+        ..optional
+    };
+
+    assert!(optional.validate(&[]).is_empty());
+    assert_eq!(
+        required.validate(&[]),
+        vec!["At least one role is required.".to_string()]
+    );
+    assert!(required.validate(&[Uuid::new_v4()]).is_empty());
+}
+
+#[test]
+pub async fn uuid_list_size_bounds() {
+    use lighter_common::prelude::Uuid;
+
+    use crate::requests::v1::UuidListRules;
+
+    let rules = UuidListRules {
+        item: "role",
+        required: false,
+        max: 2,
+        unique: false,
+    };
+    let ids = (0..3).map(|_| Uuid::new_v4()).collect::<Vec<_>>();
+
+    assert!(rules.validate(&ids[..2]).is_empty());
+    assert_eq!(
+        rules.validate(&ids),
+        vec!["At most 2 roles can be assigned.".to_string()]
+    );
+    assert_eq!(
+        UuidListRules { max: 0, ..rules }.validate(&ids[..1]).len(),
+        1
+    );
+}
@@ -0,0 +1,83 @@
+#[test]
+pub async fn role_update_invalidates_sessions() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::{base58, prelude::*};
+
+    use crate::requests::v1::role::RoleRequest;
+    use crate::responses::v1::cache::CacheStats;
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .permission("before")
+        .permission("after")
+        .role("parent", &["before"])
+        .child_role("child", Some("parent"), &[])
+        .user("member", &["child"], &[])
+        .build(&db)
+        .await;
+    let session = base58::to_string(world.user("member").generate_token(&db, None).await?.id);
+    let admin = token(&db).await;
+    let stats = || {
+        TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", admin)))
+            .uri("/v1/admin/cache/stats")
+            .to_request()
+    };
+    let codes = |auth: serde_json::Value| -> Vec<String> {
+        auth["permissions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|permission| permission["code"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    let before: CacheStats = read_body_json(call_service(&service, stats()).await).await;
+
+    for _ in 0..2 {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", session)))
+            .uri("/user")
+            .to_request();
+
+        let auth = read_body_json(call_service(&service, request).await).await;
+
+        assert_eq!(codes(auth), vec![world.permission("before").code.clone()]);
+    }
+
+    // only the first lookup of the session goes to the database
+    let after: CacheStats = read_body_json(call_service(&service, stats()).await).await;
+
+    assert_eq!(after.misses, before.misses + 1);
+    assert_eq!(after.hits, before.hits + 2);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", admin)))
+        .method(Method::PUT)
+        .uri(format!("/v1/role/{}", world.role("parent").id).as_str())
+        .set_json(&RoleRequest {
+            name: world.role("parent").name.clone(),
+            permissions: vec![world.permission("after").id],
+            parent_id: None,
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // the inherited permission change reaches the cached session
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", session)))
+        .uri("/user")
+        .to_request();
+
+    let auth = read_body_json(call_service(&service, request).await).await;
+
+    assert_eq!(codes(auth), vec![world.permission("after").code.clone()]);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+#[test]
+pub async fn delete_cleans_up_links() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+
+    use crate::entities::v1::{permission_role, role_user, roles};
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .permission("granted")
+        .role("doomed", &["granted"])
+        .child_role("orphan", Some("doomed"), &[])
+        .user("member", &["doomed"], &[])
+        .build(&db)
+        .await;
+    let role = world.role("doomed");
+    let member = world.user("member");
+
+    assert_eq!(member.permissions(&db).await?.len(), 1);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::DELETE)
+        .uri(format!("/v1/role/{}", role.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let permission_roles = permission_role::Entity::find()
+        .filter(permission_role::Column::RoleId.eq(role.id))
+        .count(&db)
+        .await?;
+    let role_users = role_user::Entity::find()
+        .filter(role_user::Column::RoleId.eq(role.id))
+        .count(&db)
+        .await?;
+    let orphan = roles::Entity::find_by_id(world.role("orphan").id)
+        .one(&db)
+        .await?
+        .unwrap();
+
+    assert_eq!(permission_roles, 0);
+    assert_eq!(role_users, 0);
+    assert_eq!(orphan.parent_id, None);
+    assert!(member.permissions(&db).await?.is_empty());
+
+    Ok(())
+}
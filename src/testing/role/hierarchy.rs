@@ -0,0 +1,106 @@
+#[test]
+pub async fn inherit_parent_permissions() -> Result<(), lighter_common::prelude::Error> {
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::{permissions, roles, users};
+
+    let (_, db) = crate::service!();
+    let suffix = Uuid::new_v4().simple().to_string().to_uppercase();
+    let permission = permissions::Model {
+        id: Uuid::new_v4(),
+        code: format!("INHERITED_{}", suffix),
+        name: format!("inherited {}", suffix),
+    }
+    .store(&db)
+    .await?;
+    let parent = roles::Model {
+        id: Uuid::new_v4(),
+        code: format!("PARENT_{}", suffix),
+        name: format!("parent {}", suffix),
+        parent_id: None,
+    }
+    .store(&db, vec![permission.clone()])
+    .await?;
+    let child = roles::Model {
+        id: Uuid::new_v4(),
+        code: format!("CHILD_{}", suffix),
+        name: format!("child {}", suffix),
+        parent_id: Some(parent.id),
+    }
+    .store(&db, vec![])
+    .await?;
+
+    let id = Uuid::new_v4();
+    let user = users::Model {
+        id,
+        name: "role hierarchy".to_string(),
+        email: format!("{}@local", id),
+        email_verified_at: None,
+        username: id.simple().to_string(),
+        password: Hash::make(id, "password").to_string(),
+        profile_photo_id: None,
+        created_at: now(),
+        updated_at: now(),
+        deleted_at: None,
+        last_login_at: None,
+    }
+    .store(&db, vec![], vec![child])
+    .await?;
+
+    let permissions = user.permissions(&db).await?;
+
+    assert!(permissions.iter().any(|p| p.id == permission.id));
+
+    Ok(())
+}
+
+#[test]
+pub async fn reject_cycle() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::roles;
+    use crate::requests::v1::role::RoleRequest;
+
+    let (service, db) = crate::service!();
+    let suffix = Uuid::new_v4().simple().to_string().to_uppercase();
+    let parent = roles::Model {
+        id: Uuid::new_v4(),
+        code: format!("PARENT_{}", suffix),
+        name: format!("parent {}", suffix),
+        parent_id: None,
+    }
+    .store(&db, vec![])
+    .await?;
+    let child = roles::Model {
+        id: Uuid::new_v4(),
+        code: format!("CHILD_{}", suffix),
+        name: format!("child {}", suffix),
+        parent_id: Some(parent.id),
+    }
+    .store(&db, vec![])
+    .await?;
+
+    for parent_id in [child.id, parent.id] {
+        let request = TestRequest::default()
+            .method(Method::PUT)
+            .uri(format!("/v1/role/{}", parent.id).as_str())
+            .set_json(&RoleRequest {
+                name: parent.name.clone(),
+                permissions: vec![],
+                parent_id: Some(parent_id),
+            })
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert!(response.status().is_client_error());
+    }
+
+    let parent = roles::Model::find_by_id(&db, parent.id).await?.unwrap();
+
+    assert_eq!(parent.parent_id, None);
+
+    Ok(())
+}
@@ -0,0 +1,6 @@
+pub mod cache;
+pub mod delete;
+pub mod hierarchy;
+pub mod pagination;
+pub mod show;
+pub mod store;
@@ -0,0 +1,65 @@
+#[test]
+pub async fn role_sorting() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::responses::v1::role::{
+        RolePaginationOrder, RolePaginationResponse, RolePaginationSort,
+    };
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .role("bravo", &[])
+        .role("alpha", &[])
+        .role("charlie", &[])
+        .build(&db)
+        .await;
+    let suffix = world.role("alpha").name.replace("alpha ", "");
+    let value = |value: serde_json::Value| value.as_str().unwrap().to_string();
+    let order = value(serde_json::to_value(RolePaginationOrder::Name)?);
+
+    for (sort, expected) in [
+        (RolePaginationSort::Asc, ["alpha", "bravo", "charlie"]),
+        (RolePaginationSort::Desc, ["charlie", "bravo", "alpha"]),
+    ] {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+            .uri(&format!(
+                "/v1/role?search={}&order={}&sort={}",
+                suffix,
+                order,
+                value(serde_json::to_value(sort)?),
+            ))
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let page: RolePaginationResponse = read_body_json(response).await;
+        let names = page
+            .data
+            .into_iter()
+            .map(|role| role.name)
+            .collect::<Vec<_>>();
+        let expected = expected
+            .iter()
+            .map(|name| world.role(name).name.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(names, expected);
+    }
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .uri("/v1/role?order=parent_id")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    Ok(())
+}
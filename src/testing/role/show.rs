@@ -0,0 +1,75 @@
+#[test]
+pub async fn show_with_permissions() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::body::MessageBody;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::{permissions, roles};
+    use crate::responses::v1::role::{RoleWithPermissions, RoleWithPermissionsPaginationResponse};
+
+    let (service, db) = crate::service!();
+    let suffix = Uuid::new_v4().simple().to_string().to_uppercase();
+    let permission = permissions::Model {
+        id: Uuid::new_v4(),
+        code: format!("EXPANDED_{}", suffix),
+        name: format!("expanded {}", suffix),
+    }
+    .store(&db)
+    .await?;
+    let role = roles::Model {
+        id: Uuid::new_v4(),
+        code: format!("EXPANDED_{}", suffix),
+        name: format!("expanded {}", suffix),
+        parent_id: None,
+    }
+    .store(&db, vec![permission.clone()])
+    .await?;
+
+    let request = TestRequest::default()
+        .uri(format!("/v1/role/{}", role.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+    let lean = serde_json::from_slice::<serde_json::Value>(&body)?;
+
+    assert!(lean.get("permissions").is_none());
+
+    let request = TestRequest::default()
+        .uri(format!("/v1/role/{}?include=permissions", role.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+    let expanded = serde_json::from_slice::<RoleWithPermissions>(&body)?;
+    let codes = expanded
+        .permissions
+        .iter()
+        .map(|permission| permission.code.clone())
+        .collect::<Vec<_>>();
+
+    assert_eq!(codes, vec![permission.code.clone()]);
+
+    let request = TestRequest::default()
+        .uri(format!("/v1/role?search={}&include=permissions", role.code).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+    let page = serde_json::from_slice::<RoleWithPermissionsPaginationResponse>(&body)?;
+    let expanded = page.data.iter().find(|r| r.id == role.id).unwrap();
+
+    assert_eq!(expanded.permissions.len(), 1);
+    assert_eq!(expanded.permissions[0].code, permission.code);
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+#[test]
+pub async fn store_and_update_permissions() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::body::MessageBody;
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::{permissions, roles};
+    use crate::requests::v1::role::RoleRequest;
+    use crate::responses::v1::role::Role;
+
+    let (service, db) = crate::service!();
+    let suffix = Uuid::new_v4().simple().to_string().to_uppercase();
+    let mut created = vec![];
+
+    for code in ["FIRST", "SECOND"] {
+        let permission = permissions::Model {
+            id: Uuid::new_v4(),
+            code: format!("{}_{}", code, suffix),
+            name: format!("{} {}", code, suffix),
+        }
+        .store(&db)
+        .await?;
+
+        created.push(permission);
+    }
+
+    let name = format!("role {}", suffix);
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/v1/role")
+        .set_json(&RoleRequest {
+            name: name.clone(),
+            permissions: vec![created[0].id, Uuid::new_v4()],
+            parent_id: None,
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/v1/role")
+        .set_json(&RoleRequest {
+            name: name.clone(),
+            permissions: vec![created[0].id],
+            parent_id: None,
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+    let status = response.status();
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+    assert!(status.is_success(), "{:?}", body);
+
+    let role = serde_json::from_slice::<Role>(&body)?;
+    let role = roles::Model::find_by_id(&db, role.id).await?.unwrap();
+    let permissions = role.permissions(&db).await?;
+
+    assert_eq!(permissions, vec![created[0].clone()]);
+
+    let request = TestRequest::default()
+        .method(Method::PUT)
+        .uri(format!("/v1/role/{}", role.id).as_str())
+        .set_json(&RoleRequest {
+            name,
+            permissions: vec![created[1].id],
+            parent_id: None,
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_success());
+
+    let permissions = role.permissions(&db).await?;
+
+    assert_eq!(permissions, vec![created[1].clone()]);
+
+    Ok(())
+}
+
+#[test]
+pub async fn store_rejects_repeated_permissions() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::roles;
+    use crate::requests::v1::role::RoleRequest;
+    use crate::testing::fixtures::World;
+
+    let (service, db) = crate::service!();
+    let world = World::new().permission("repeated").build(&db).await;
+    let permission = world.permission("repeated");
+    let suffix = Uuid::new_v4().simple().to_string().to_uppercase();
+    let name = format!("repeated {}", suffix);
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/v1/role")
+        .set_json(&RoleRequest {
+            name: name.clone(),
+            permissions: vec![permission.id, permission.id],
+            parent_id: None,
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let code = name.replace(" ", "_").to_uppercase();
+
+    assert!(!roles::Model::code_exist(&db, &code).await);
+
+    Ok(())
+}
@@ -0,0 +1,162 @@
+#[test]
+pub async fn unique_ids() {
+    use lighter_common::prelude::Uuid;
+
+    use crate::entities::v1::users::Model;
+
+    let a = Uuid::new_v4();
+    let b = Uuid::new_v4();
+
+    assert_eq!(Model::unique_ids(&[a, b, a, a, b]), vec![a, b]);
+    assert!(Model::unique_ids(&[]).is_empty());
+}
+
+#[test]
+pub async fn default_cap() {
+    use crate::config::AuthConfig;
+
+    assert_eq!(AuthConfig::default().max_assignments, 100);
+}
+
+#[test]
+pub async fn oversized() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::instance::token;
+
+    let suffix = Uuid::new_v4().simple().to_string();
+    let payload = UserStoreRequest {
+        name: "John Doe".to_string(),
+        email: format!("{}@local", suffix),
+        username: suffix.clone(),
+        password: "password".to_string(),
+        password_confirmation: "password".to_string(),
+        profile_photo_id: None,
+        permissions: (0..3).map(|_| Uuid::new_v4()).collect(),
+        roles: vec![],
+    };
+
+    let (service, db) = crate::service!(AuthConfig {
+        max_assignments: 2,
+        ..AuthConfig::default()
+    });
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .uri("/v1/user")
+        .method(Method::POST)
+        .set_json(&payload)
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    Ok(())
+}
+
+#[test]
+pub async fn oversized_grant() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+    use crate::requests::v1::user::UserPermissionsRequest;
+    use crate::testing::instance::{token, user};
+
+    let (service, db) = crate::service!(AuthConfig {
+        max_assignments: 2,
+        ..AuthConfig::default()
+    });
+    let user = user(&db, &[]).await;
+    // granting directly goes through the same cap as creating the user
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .uri(format!("/v1/user/{}/permissions", user.id).as_str())
+        .method(Method::POST)
+        .set_json(&UserPermissionsRequest {
+            permissions: (0..3).map(|_| Uuid::new_v4()).collect(),
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body(response).await;
+
+    assert!(String::from_utf8_lossy(&body).contains("At most 2 permissions"));
+
+    Ok(())
+}
+
+#[test]
+pub async fn duplicates() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+
+    use crate::config::AuthConfig;
+    use crate::entities::v1::{permission_user, role_user, users};
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!(AuthConfig {
+        max_assignments: 2,
+        ..AuthConfig::default()
+    });
+    let world = World::new()
+        .permission("assignment")
+        .role("assignment", &[])
+        .build(&db)
+        .await;
+    let permission = world.permission("assignment").id;
+    let role = world.role("assignment").id;
+    let suffix = Uuid::new_v4().simple().to_string();
+    // repeated ids count once against the cap
+    let payload = UserStoreRequest {
+        name: "John Doe".to_string(),
+        email: format!("{}@local", suffix),
+        username: suffix.clone(),
+        password: "password".to_string(),
+        password_confirmation: "password".to_string(),
+        profile_photo_id: None,
+        permissions: vec![permission, permission, permission],
+        roles: vec![role, role, role],
+    };
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .uri("/v1/user")
+        .method(Method::POST)
+        .set_json(&payload)
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let user = users::Entity::find()
+        .filter(users::Column::Username.eq(suffix))
+        .one(&db)
+        .await?
+        .unwrap();
+    let permissions = permission_user::Entity::find()
+        .filter(permission_user::Column::UserId.eq(user.id))
+        .count(&db)
+        .await?;
+    let roles = role_user::Entity::find()
+        .filter(role_user::Column::UserId.eq(user.id))
+        .count(&db)
+        .await?;
+
+    assert_eq!(permissions, 1);
+    assert_eq!(roles, 1);
+
+    Ok(())
+}
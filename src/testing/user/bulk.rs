@@ -0,0 +1,112 @@
+#[test]
+pub async fn bulk() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::users::Model;
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::responses::v1::user::bulk::BulkUserResponse;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let payload = |username: &str, email: String| UserStoreRequest {
+        name: "John Doe".to_string(),
+        email,
+        username: username.to_string(),
+        password: "password".to_string(),
+        password_confirmation: "password".to_string(),
+        profile_photo_id: None,
+        permissions: vec![],
+        roles: vec![],
+    };
+    let usernames = (0..4)
+        .map(|_| Uuid::new_v4().simple().to_string())
+        .collect::<Vec<_>>();
+    let payloads = usernames
+        .iter()
+        .enumerate()
+        .map(|(index, username)| match index {
+            2 => payload(username, "not an email".to_string()),
+            _ => payload(username, format!("{}@local", username)),
+        })
+        .collect::<Vec<_>>();
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user/bulk")
+        .set_json(&payloads)
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response: BulkUserResponse = read_body_json(response).await;
+
+    assert_eq!(response.created, 3);
+    assert_eq!(response.failed, 1);
+    assert_eq!(response.results.len(), 4);
+
+    for (index, result) in response.results.iter().enumerate() {
+        assert_eq!(result.index, index);
+        assert_eq!(result.success, index != 2);
+        assert_eq!(
+            Model::username_exists(&db, &usernames[index]).await,
+            index != 2
+        );
+    }
+
+    let failure = &response.results[2];
+
+    assert!(StatusCode::from_u16(failure.status)
+        .unwrap()
+        .is_client_error());
+    assert!(failure.body.to_string().contains("email"));
+
+    Ok(())
+}
+
+#[test]
+pub async fn oversized() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!(AuthConfig {
+        max_bulk_users: 1,
+        ..AuthConfig::default()
+    });
+    let payloads = (0..2)
+        .map(|_| {
+            let username = Uuid::new_v4().simple().to_string();
+
+            UserStoreRequest {
+                name: "John Doe".to_string(),
+                email: format!("{}@local", username),
+                username,
+                password: "password".to_string(),
+                password_confirmation: "password".to_string(),
+                profile_photo_id: None,
+                permissions: vec![],
+                roles: vec![],
+            }
+        })
+        .collect::<Vec<_>>();
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user/bulk")
+        .set_json(&payloads)
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    Ok(())
+}
@@ -0,0 +1,185 @@
+#[test]
+pub async fn change_email() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::body::MessageBody;
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+    use crate::entities::v1::users;
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::requests::v1::user::UserEmailRequest;
+    use crate::services::v1::notification::Notifications;
+    use crate::testing::instance::{authed_request, user, Outbox};
+
+    let outbox = Outbox::new();
+    let (service, db) = crate::service!(AuthConfig::env(), Notifications::new(outbox.clone()));
+    let user = user(&db, &[]).await;
+    let old = user.email.clone();
+    let new = format!("{}@changed", Uuid::new_v4());
+    let request = authed_request(&db, &user)
+        .await
+        .method(Method::POST)
+        .uri(format!("/v1/user/{}/email", user.id).as_str())
+        .set_json(&UserEmailRequest { email: new.clone() })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+    let status = response.status();
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+    assert_eq!(status, StatusCode::ACCEPTED, "{:?}", body);
+
+    // the token goes to the new address only, never back to the requester
+    let token = outbox.token(&new).unwrap();
+
+    assert!(outbox.token(&old).is_none());
+    assert!(!String::from_utf8_lossy(&body).contains(&token));
+
+    let login = |email: &str| {
+        TestRequest::default()
+            .method(Method::POST)
+            .uri("/login")
+            .set_json(&LoginRequest {
+                email_or_username: email.to_string(),
+                password: "password".to_string(),
+            })
+            .to_request()
+    };
+
+    // pending, the old email keeps working
+    let response = call_service(&service, login(&old)).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = call_service(&service, login(&new)).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let pending = users::Model::find_by_id(&db, user.id).await.unwrap();
+
+    assert_eq!(pending.email, old);
+    assert!(pending.email_verified_at.is_none());
+
+    let request = TestRequest::default()
+        .uri(format!("/v1/auth/verify-email?token={}", token).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let confirmed = users::Model::find_by_id(&db, user.id).await.unwrap();
+
+    assert_eq!(confirmed.email, new);
+    assert!(confirmed.email_verified_at.is_some());
+
+    let response = call_service(&service, login(&new)).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let response = call_service(&service, login(&old)).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[test]
+pub async fn change_email_taken() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::call_service;
+    use lighter_common::prelude::*;
+
+    use crate::requests::v1::user::UserEmailRequest;
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let user = user(&db, &[]).await;
+    let other = crate::testing::instance::user(&db, &[]).await;
+    let request = authed_request(&db, &user)
+        .await
+        .method(Method::POST)
+        .uri(format!("/v1/user/{}/email", user.id).as_str())
+        .set_json(&UserEmailRequest {
+            email: other.email.clone(),
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // changing someone else's email needs UPDATE_USER
+    let request = authed_request(&db, &user)
+        .await
+        .method(Method::POST)
+        .uri(format!("/v1/user/{}/email", other.id).as_str())
+        .set_json(&UserEmailRequest {
+            email: format!("{}@changed", Uuid::new_v4()),
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[test]
+pub async fn change_email_across_workers() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::App;
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::users;
+    use crate::requests::v1::user::UserEmailRequest;
+    use crate::router::{configure, Shared};
+    use crate::services::v1::notification::Notifications;
+    use crate::testing::instance::{authed_request, database, user, Outbox};
+
+    let db = database().await?;
+    let shared = Shared::new();
+    let outbox = Outbox::new();
+    let worker = || {
+        App::new()
+            .app_data(Data::new(db.clone()))
+            .configure(|app| configure(app, &shared))
+            .app_data(Data::new(Notifications::new(outbox.clone())))
+    };
+    let first = init_service(worker()).await;
+    let second = init_service(worker()).await;
+    let user = user(&db, &[]).await;
+    let new = format!("{}@changed", Uuid::new_v4());
+    let request = authed_request(&db, &user)
+        .await
+        .method(Method::POST)
+        .uri(format!("/v1/user/{}/email", user.id).as_str())
+        .set_json(&UserEmailRequest { email: new.clone() })
+        .to_request();
+    let response = call_service(&first, request).await;
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+    // confirmed on another worker than the one holding the request
+    let request = TestRequest::default()
+        .uri(
+            format!(
+                "/v1/auth/verify-email?token={}",
+                outbox.token(&new).unwrap()
+            )
+            .as_str(),
+        )
+        .to_request();
+    let response = call_service(&second, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        users::Model::find_by_id(&db, user.id).await.unwrap().email,
+        new
+    );
+
+    Ok(())
+}
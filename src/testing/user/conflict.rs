@@ -0,0 +1,113 @@
+#[test]
+pub async fn store_conflict() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::responses::v1::conflict::Conflict;
+    use crate::testing::instance::user;
+
+    let (service, db) = crate::service!();
+    let existing = user(&db, &[]).await;
+    let fresh = Uuid::new_v4().simple().to_string();
+
+    for (email, username, field) in [
+        (existing.email.clone(), fresh.clone(), "email"),
+        (
+            format!("{}@local", fresh),
+            existing.username.clone(),
+            "username",
+        ),
+    ] {
+        let request = TestRequest::default()
+            .method(Method::POST)
+            .uri("/v1/user")
+            .set_json(&UserStoreRequest {
+                name: "conflict".to_string(),
+                email,
+                username,
+                password: "password".to_string(),
+                password_confirmation: "password".to_string(),
+                profile_photo_id: None,
+                permissions: vec![],
+                roles: vec![],
+            })
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let conflict: Conflict = read_body_json(response).await;
+
+        assert_eq!(conflict.field, field);
+    }
+
+    Ok(())
+}
+
+#[test]
+pub async fn store_conflict_race() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::TestRequest;
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+    use crate::entities::v1::users;
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::services::v1::user::store::store;
+
+    let (_, db) = crate::service!();
+    let id = Uuid::new_v4().simple().to_string();
+    let handles = ["a", "b"].map(|suffix| {
+        let db = db.clone();
+        let request = UserStoreRequest {
+            name: "racer".to_string(),
+            email: format!("{}@local", id),
+            username: format!("{}_{}", id, suffix),
+            password: "password".to_string(),
+            password_confirmation: "password".to_string(),
+            profile_photo_id: None,
+            permissions: vec![],
+            roles: vec![],
+        };
+
+        actix_web::rt::spawn(async move {
+            let req = TestRequest::default().to_http_request();
+
+            store(&db, &AuthConfig::default(), request)
+                .await
+                .respond_to(&req)
+                .status()
+        })
+    });
+    let mut statuses = vec![];
+
+    for handle in handles {
+        statuses.push(handle.await.unwrap());
+    }
+
+    statuses.sort();
+
+    // both may pass the existence check, the loser still gets 409 instead of 500
+    assert_eq!(statuses, [StatusCode::OK, StatusCode::CONFLICT]);
+
+    // the violation raised by the constraint itself names the field
+    let existing = users::Model::find_by_email(&db, format!("{}@local", id))
+        .await
+        .unwrap();
+    let duplicate = users::Model {
+        id: Uuid::new_v4(),
+        username: Uuid::new_v4().simple().to_string(),
+        ..existing
+    };
+    let e = match duplicate.store(&db, vec![], vec![]).await {
+        Err(sea_orm::TransactionError::Transaction(e)) => e,
+        Err(sea_orm::TransactionError::Connection(e)) => e,
+        Ok(_) => panic!("duplicate email was stored"),
+    };
+
+    assert_eq!(users::Model::unique_violation(&e), Some("email"));
+
+    Ok(())
+}
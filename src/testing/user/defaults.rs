@@ -0,0 +1,63 @@
+#[test]
+pub async fn defaults() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::responses::v1::user::complete::UserWithPermissionAndRole;
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (_, db) = crate::service!();
+    let world = World::new()
+        .permission("default")
+        .role("default", &[])
+        .role("explicit", &[])
+        .build(&db)
+        .await;
+    let permission = world.permission("default");
+    let role = world.role("default");
+    let explicit = world.role("explicit");
+    let (service, db) = crate::service!(AuthConfig {
+        default_roles: vec![role.code.clone()],
+        default_permissions: vec![permission.code.clone(), "NOT_A_PERMISSION".to_string()],
+        ..AuthConfig::default()
+    });
+    let admin = token(&db).await;
+
+    // (explicit roles, expected role)
+    for (roles, expected) in [(vec![], role), (vec![explicit.id], explicit)] {
+        let username = Uuid::new_v4().simple().to_string();
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", admin)))
+            .method(Method::POST)
+            .uri("/v1/user")
+            .set_json(&UserStoreRequest {
+                name: "John Doe".to_string(),
+                email: format!("{}@local", username),
+                username,
+                password: "password".to_string(),
+                password_confirmation: "password".to_string(),
+                profile_photo_id: None,
+                permissions: vec![],
+                roles,
+            })
+            .to_request();
+
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let user: UserWithPermissionAndRole = read_body_json(response).await;
+
+        // unknown default codes are skipped, permissions default independently of roles
+        assert_eq!(user.roles.len(), 1);
+        assert_eq!(user.roles[0].id, expected.id);
+        assert_eq!(user.permissions.len(), 1);
+        assert_eq!(user.permissions[0].id, permission.id);
+    }
+
+    Ok(())
+}
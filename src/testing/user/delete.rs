@@ -0,0 +1,93 @@
+#[test]
+pub async fn delete_rejects_existing_tokens() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::{base58, prelude::*};
+
+    use crate::testing::instance::{token, user};
+
+    let (service, db) = crate::service!();
+    let user = user(&db, &[]).await;
+    let session = base58::to_string(user.generate_token(&db, None).await?.id);
+    let me = || {
+        TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", session)))
+            .uri("/user")
+            .to_request()
+    };
+
+    // cached by the first request
+    let response = call_service(&service, me()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::DELETE)
+        .uri(format!("/v1/user/{}", user.id).as_str())
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // evicted from the cache, and the database lookup doesn't revive it
+    let response = call_service(&service, me()).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = call_service(&service, me()).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
+#[test]
+pub async fn delete_rejects_tokens_on_every_worker() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::App;
+    use lighter_common::{base58, prelude::*};
+
+    use crate::router::{configure, Shared};
+    use crate::testing::instance::{database, token, user};
+
+    let db = database().await?;
+    let shared = Shared::new();
+    let worker = || {
+        App::new()
+            .app_data(Data::new(db.clone()))
+            .configure(|app| configure(app, &shared))
+    };
+    let first = init_service(worker()).await;
+    let second = init_service(worker()).await;
+    let user = user(&db, &[]).await;
+    let session = base58::to_string(user.generate_token(&db, None).await?.id);
+    let me = || {
+        TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", session)))
+            .uri("/user")
+            .to_request()
+    };
+
+    // cached through the second worker
+    let response = call_service(&second, me()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::DELETE)
+        .uri(format!("/v1/user/{}", user.id).as_str())
+        .to_request();
+    let response = call_service(&first, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // the eviction by the first worker reaches the second
+    let response = call_service(&second, me()).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+#[test]
+pub async fn email_is_case_insensitive() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let local = Uuid::new_v4().simple().to_string();
+    let payload = |email: String| UserStoreRequest {
+        name: "John Doe".to_string(),
+        email,
+        username: Uuid::new_v4().simple().to_string(),
+        password: "password".to_string(),
+        password_confirmation: "password".to_string(),
+        profile_photo_id: None,
+        permissions: vec![],
+        roles: vec![],
+    };
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&payload(format!("John.{}@Example.com", local)))
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&payload(format!("john.{}@example.com", local)))
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    Ok(())
+}
+
+#[test]
+pub async fn email_canonicalize_gmail() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+    use crate::entities::v1::users::Model;
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::instance::token;
+
+    assert_eq!(
+        Model::normalize_email("J.Doe+news@Gmail.com", false),
+        "j.doe+news@gmail.com"
+    );
+    assert_eq!(
+        Model::normalize_email("J.Doe+news@Gmail.com", true),
+        "jdoe@gmail.com"
+    );
+    assert_eq!(
+        Model::normalize_email("j.doe+news@example.com", true),
+        "j.doe+news@example.com"
+    );
+
+    let (service, db) = crate::service!(AuthConfig {
+        canonicalize_gmail: true,
+        ..AuthConfig::default()
+    });
+    let local = Uuid::new_v4().simple().to_string();
+    let payload = |email: String| UserStoreRequest {
+        name: "John Doe".to_string(),
+        email,
+        username: Uuid::new_v4().simple().to_string(),
+        password: "password".to_string(),
+        password_confirmation: "password".to_string(),
+        profile_photo_id: None,
+        permissions: vec![],
+        roles: vec![],
+    };
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&payload(format!("j.{}+news@gmail.com", local)))
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(Model::find_by_email(&db, format!("j{}@gmail.com", local))
+        .await
+        .is_some());
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&payload(format!("j{}@gmail.com", local)))
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    Ok(())
+}
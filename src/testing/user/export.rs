@@ -0,0 +1,83 @@
+#[test]
+pub async fn export() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, read_body_json};
+    use lighter_common::{base58, prelude::*};
+    use serde_json::Value;
+
+    use crate::entities::v1::{audit_logs, tokens};
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let owner = user(&db, &[]).await;
+
+    audit_logs::Model {
+        id: Uuid::new_v4(),
+        actor_user_id: Some(owner.id),
+        action: "login".to_string(),
+        target_type: Some("user".to_string()),
+        target_id: Some(owner.id),
+        ip: None,
+        metadata: None,
+        created_at: now(),
+    }
+    .store(&db)
+    .await?;
+
+    // self-service, no READ_USER needed
+    let request = authed_request(&db, &owner)
+        .await
+        .uri(&format!("/v1/user/{}/export", owner.id))
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: Value = read_body_json(response).await;
+
+    for section in ["profile", "roles", "permissions", "sessions", "auditLogs"] {
+        assert!(body.get(section).is_some(), "missing {}", section);
+    }
+
+    assert_eq!(body["profile"]["id"], owner.id.to_string());
+    assert_eq!(body["auditLogs"].as_array().unwrap().len(), 1);
+    assert!(!body["sessions"].as_array().unwrap().is_empty());
+
+    let raw = body.to_string();
+
+    assert!(!raw.contains("password"));
+    assert!(!raw.contains(&owner.password));
+
+    // someone else's data needs READ_USER
+    let stranger = user(&db, &[]).await;
+    let request = authed_request(&db, &stranger)
+        .await
+        .uri(&format!("/v1/user/{}/export", owner.id))
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let reader = user(&db, &["READ_USER"]).await;
+    let request = authed_request(&db, &reader)
+        .await
+        .uri(&format!("/v1/user/{}/export", owner.id))
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // sessions are listed by handle, none of the owner's bearer tokens leak
+    let body: Value = read_body_json(response).await;
+    let raw = body.to_string();
+    let sessions = tokens::Model::active(&db, owner.id).await?;
+
+    assert_eq!(body["sessions"].as_array().unwrap().len(), sessions.len());
+
+    for token in sessions {
+        assert!(!raw.contains(&base58::to_string(token.id)));
+        assert!(!raw.contains(&token.id.to_string()));
+        assert!(raw.contains(&token.session_id.to_string()));
+    }
+
+    Ok(())
+}
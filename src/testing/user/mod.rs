@@ -1,5 +1,22 @@
+pub mod assignments;
+pub mod bulk;
+pub mod change_email;
+pub mod conflict;
+pub mod defaults;
+pub mod delete;
+pub mod email;
+pub mod export;
 pub mod pagination;
+pub mod password_history;
+pub mod permissions;
+pub mod purge;
+pub mod references;
+pub mod restore;
+pub mod session_count;
 pub mod show;
 pub mod store;
+pub mod store_with_codes;
 pub mod update_general_information;
 pub mod update_password;
+pub mod username;
+pub mod validation;
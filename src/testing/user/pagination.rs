@@ -25,3 +25,72 @@ pub async fn paginate() -> Result<(), lighter_common::prelude::Error> {
 
     Ok(())
 }
+
+#[test]
+pub async fn paginate_soft_deleted() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::body::MessageBody;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::users;
+    use crate::responses::v1::user::simple::UserPaginationResponse;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let token = token(&db).await;
+
+    for username in ["jane_active", "jane_deleted"] {
+        let id = Uuid::new_v4();
+        let user = users::Model {
+            id,
+            name: username.to_string(),
+            email: format!("{}@local", username),
+            email_verified_at: None,
+            username: username.to_string(),
+            password: Hash::make(id, "password").to_string(),
+            profile_photo_id: None,
+            created_at: now(),
+            updated_at: now(),
+            deleted_at: None,
+            last_login_at: None,
+        }
+        .store(&db, vec![], vec![])
+        .await?;
+
+        if username == "jane_deleted" {
+            user.soft_delete(&db).await?;
+        }
+    }
+
+    for (uri, expected) in [
+        ("/v1/user?search=JANE", vec!["jane_active"]),
+        (
+            "/v1/user?search=jane&include_deleted=true",
+            vec!["jane_active", "jane_deleted"],
+        ),
+    ] {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .uri(uri)
+            .to_request();
+
+        let response = call_service(&service, request).await;
+        let status = response.status();
+        let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+        assert_eq!(status, StatusCode::OK, "{:?}", body);
+
+        let body = serde_json::from_slice::<UserPaginationResponse>(&body)?;
+        let mut usernames = body
+            .data
+            .iter()
+            .map(|user| user.username.as_str())
+            .collect::<Vec<_>>();
+
+        usernames.sort();
+
+        assert_eq!(usernames, expected);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use actix_web::http::Method;
+use actix_web::test::TestRequest;
+use lighter_common::prelude::Uuid;
+
+use crate::requests::v1::user::UserUpdatePasswordRequest;
+
+fn change(token: &str, current: &str, new: &str) -> TestRequest {
+    TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .set_json(&UserUpdatePasswordRequest {
+            current_password: current.to_string(),
+            new_password: new.to_string(),
+            password_confirmation: new.to_string(),
+        })
+        .method(Method::PUT)
+        .uri(format!("/v1/user/{}/password", Uuid::from_u128(0)).as_str())
+}
+
+#[test]
+pub async fn password_history() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::call_service;
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!(AuthConfig {
+        password_history_size: 1,
+        ..AuthConfig::default()
+    });
+    let token = token(&db).await;
+
+    for (current, new, accepted) in [
+        ("password", "password", false),
+        ("password", "second password", true),
+        ("second password", "password", false),
+        ("second password", "third password", true),
+        // older than the retained history
+        ("third password", "password", true),
+    ] {
+        let request = change(&token, current, new).to_request();
+        let response = call_service(&service, request).await;
+
+        assert_eq!(
+            response.status() == StatusCode::OK,
+            accepted,
+            "{} -> {}",
+            current,
+            new
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+pub async fn password_history_disabled() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::call_service;
+    use lighter_common::prelude::*;
+
+    use crate::config::AuthConfig;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!(AuthConfig {
+        password_history_size: 0,
+        ..AuthConfig::default()
+    });
+    let token = token(&db).await;
+    let request = change(&token, "password", "password").to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+#[test]
+pub async fn assign_and_revoke_permissions() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::body::MessageBody;
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::{base58, prelude::*};
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+
+    use crate::entities::v1::permission_user;
+    use crate::requests::v1::user::UserPermissionsRequest;
+    use crate::responses::v1::user::complete::UserWithPermissionAndRole;
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .permission("first")
+        .permission("second")
+        .user("bulk permissions", &[], &[])
+        .build(&db)
+        .await;
+    let created = [world.permission("first"), world.permission("second")];
+    let user = world.user("bulk permissions");
+    let session = base58::to_string(user.generate_token(&db, None).await?.id);
+    let admin = token(&db).await;
+    let codes = |body: &[u8]| -> Vec<String> {
+        let auth = serde_json::from_slice::<serde_json::Value>(body).unwrap();
+
+        auth["permissions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|permission| permission["code"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // cache the session before permissions change
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", session)))
+        .uri("/user")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+    assert!(codes(&body).is_empty());
+
+    for permissions in [
+        vec![created[0].id, created[1].id, created[0].id],
+        vec![created[0].id],
+    ] {
+        let request = TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", admin)))
+            .method(Method::POST)
+            .uri(format!("/v1/user/{}/permissions", user.id).as_str())
+            .set_json(&UserPermissionsRequest { permissions })
+            .to_request();
+
+        let response = call_service(&service, request).await;
+        let status = response.status();
+        let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+        assert_eq!(status, StatusCode::OK, "{:?}", body);
+
+        let user = serde_json::from_slice::<UserWithPermissionAndRole>(&body)?;
+
+        assert_eq!(user.permissions.len(), 2);
+    }
+
+    let rows = permission_user::Entity::find()
+        .filter(permission_user::Column::UserId.eq(user.id))
+        .count(&db)
+        .await?;
+
+    assert_eq!(rows, 2);
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", admin)))
+        .method(Method::POST)
+        .uri(format!("/v1/user/{}/permissions", user.id).as_str())
+        .set_json(&UserPermissionsRequest {
+            permissions: vec![Uuid::new_v4()],
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", admin)))
+        .method(Method::DELETE)
+        .uri(format!("/v1/user/{}/permissions", user.id).as_str())
+        .set_json(&UserPermissionsRequest {
+            permissions: vec![created[0].id],
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+    assert_eq!(codes(&body), vec![created[1].code.clone()]);
+
+    // the cached session reflects the new permissions
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", session)))
+        .uri("/user")
+        .to_request();
+
+    let response = call_service(&service, request).await;
+    let body = response.into_body().boxed().try_into_bytes().unwrap();
+
+    assert_eq!(codes(&body), vec![created[1].code.clone()]);
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+#[test]
+pub async fn purge() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+
+    use crate::entities::v1::{permission_user, role_user, tokens, users};
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .permission("purged")
+        .role("purged", &[])
+        .user("purged", &["purged"], &["purged"])
+        .build(&db)
+        .await;
+    let user = world.user("purged").clone();
+
+    user.generate_token(&db, None).await?;
+
+    let admin = token(&db).await;
+    let delete = |purge: bool| {
+        TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", admin)))
+            .method(Method::DELETE)
+            .uri(&format!("/v1/user/{}?purge={}", user.id, purge))
+            .to_request()
+    };
+    let rows = || async {
+        let tokens = tokens::Entity::find()
+            .filter(tokens::Column::UserId.eq(user.id))
+            .count(&db)
+            .await
+            .unwrap();
+        let permissions = permission_user::Entity::find()
+            .filter(permission_user::Column::UserId.eq(user.id))
+            .count(&db)
+            .await
+            .unwrap();
+        let roles = role_user::Entity::find()
+            .filter(role_user::Column::UserId.eq(user.id))
+            .count(&db)
+            .await
+            .unwrap();
+        let users = users::Entity::find_by_id(user.id).count(&db).await.unwrap();
+
+        (tokens, permissions, roles, users)
+    };
+
+    // refused until the user is soft deleted
+    let response = call_service(&service, delete(true)).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(rows().await, (1, 1, 1, 1));
+
+    let response = call_service(&service, delete(false)).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(rows().await, (1, 1, 1, 1));
+
+    let response = call_service(&service, delete(true)).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(rows().await, (0, 0, 0, 0));
+
+    Ok(())
+}
+
+#[test]
+pub async fn purge_requires_permission() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::call_service;
+    use lighter_common::prelude::*;
+
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let target = user(&db, &[]).await;
+
+    target.soft_delete(&db).await?;
+
+    let caller = user(&db, &["READ_USER"]).await;
+    let request = authed_request(&db, &caller)
+        .await
+        .method(Method::DELETE)
+        .uri(&format!("/v1/user/{}?purge=true", target.id))
+        .to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+#[test]
+pub async fn unknown_references() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::users::Model;
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let world = World::new().permission("reference").build(&db).await;
+    let permission = world.permission("reference").id;
+    let bogus_permission = Uuid::new_v4();
+    let bogus_role = Uuid::new_v4();
+    let username = Uuid::new_v4().simple().to_string();
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&UserStoreRequest {
+            name: "John Doe".to_string(),
+            email: format!("{}@local", username),
+            username: username.clone(),
+            password: "password".to_string(),
+            password_confirmation: "password".to_string(),
+            profile_photo_id: None,
+            permissions: vec![permission, bogus_permission],
+            roles: vec![bogus_role],
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let body = read_body(response).await;
+    let body = String::from_utf8_lossy(&body);
+
+    // only the offending ids are listed
+    assert!(body.contains(&bogus_permission.to_string()));
+    assert!(body.contains(&bogus_role.to_string()));
+    assert!(!body.contains(&permission.to_string()));
+    assert!(!Model::username_exists(&db, &username).await);
+
+    Ok(())
+}
+
+#[test]
+pub async fn known_references() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body_json, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::responses::v1::user::complete::UserWithPermissionAndRole;
+    use crate::testing::fixtures::World;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let world = World::new()
+        .permission("reference")
+        .role("reference", &[])
+        .build(&db)
+        .await;
+    let username = Uuid::new_v4().simple().to_string();
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&UserStoreRequest {
+            name: "John Doe".to_string(),
+            email: format!("{}@local", username),
+            username,
+            password: "password".to_string(),
+            password_confirmation: "password".to_string(),
+            profile_photo_id: None,
+            permissions: vec![world.permission("reference").id],
+            roles: vec![world.role("reference").id],
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let user: UserWithPermissionAndRole = read_body_json(response).await;
+
+    assert_eq!(user.permissions.len(), 1);
+    assert_eq!(user.roles.len(), 1);
+
+    Ok(())
+}
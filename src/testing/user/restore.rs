@@ -0,0 +1,84 @@
+#[test]
+pub async fn restore() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::{base58, prelude::*};
+
+    use crate::entities::v1::users;
+    use crate::requests::v1::auth::LoginRequest;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let id = Uuid::new_v4();
+    let user = users::Model {
+        id,
+        name: "restore me".to_string(),
+        email: "restore.me@local".to_string(),
+        email_verified_at: None,
+        username: "restore_me".to_string(),
+        password: Hash::make(id, "password").to_string(),
+        profile_photo_id: None,
+        created_at: now(),
+        updated_at: now(),
+        deleted_at: None,
+        last_login_at: None,
+    }
+    .store(&db, vec![], vec![])
+    .await?;
+
+    let session = base58::to_string(user.generate_token(&db, None).await?.id);
+    let me = || {
+        TestRequest::default()
+            .insert_header(("Authorization", format!("Bearer {}", session)))
+            .uri("/user")
+            .to_request()
+    };
+
+    user.soft_delete(&db).await?;
+
+    // sessions issued before the delete are rejected meanwhile
+    let response = call_service(&service, me()).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let login = LoginRequest {
+        email_or_username: user.username.clone(),
+        password: "password".to_string(),
+    };
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/login")
+        .set_json(&login)
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri(format!("/v1/user/{}/restore", user.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let request = TestRequest::default()
+        .method(Method::POST)
+        .uri("/login")
+        .set_json(&login)
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    // and resume once the user is restored
+    let response = call_service(&service, me()).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+#[test]
+pub async fn session_count() -> Result<(), lighter_common::prelude::Error> {
+    use std::time::Duration;
+
+    use actix_web::test::{call_service, read_body_json};
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::tokens;
+    use crate::responses::v1::auth::SessionCount;
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let owner = user(&db, &[]).await;
+    let hour = Duration::from_secs(60 * 60);
+
+    owner.generate_token(&db, None).await?;
+    owner.generate_token(&db, Some(now() + hour)).await?;
+    owner.generate_token(&db, Some(now() - hour)).await?;
+    owner.generate_token(&db, Some(now() - hour * 24)).await?;
+
+    assert_eq!(tokens::Model::count_active(&db, owner.id).await?, 2);
+
+    // the session making the request is active too
+    let request = authed_request(&db, &owner)
+        .await
+        .uri(format!("/v1/user/{}/sessions/count", owner.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let count: SessionCount = read_body_json(response).await;
+
+    assert_eq!(count.count, 3);
+
+    let other = user(&db, &[]).await;
+    let request = authed_request(&db, &other)
+        .await
+        .uri(format!("/v1/user/{}/sessions/count", owner.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let reader = user(&db, &["READ_USER"]).await;
+    let request = authed_request(&db, &reader)
+        .await
+        .uri(format!("/v1/user/{}/sessions/count", owner.id).as_str())
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
@@ -0,0 +1,67 @@
+#[test]
+pub async fn store_with_codes() -> Result<(), lighter_common::prelude::Error> {
+    use lighter_common::prelude::*;
+    use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+    use crate::entities::v1::{permission_user, role_user, users};
+    use crate::testing::fixtures::World;
+
+    let (_, db) = crate::service!();
+    let world = World::new()
+        .permission("by code")
+        .role("by code", &[])
+        .build(&db)
+        .await;
+    let permission = world.permission("by code");
+    let role = world.role("by code");
+    let model = |username: &str| {
+        let id = Uuid::new_v4();
+
+        users::Model {
+            id,
+            name: "by code".to_string(),
+            email: format!("{}@local", username),
+            email_verified_at: None,
+            username: username.to_string(),
+            password: Hash::make(id, "password").to_string(),
+            profile_photo_id: None,
+            created_at: now(),
+            updated_at: now(),
+            deleted_at: None,
+            last_login_at: None,
+        }
+    };
+
+    let username = Uuid::new_v4().simple().to_string();
+    let user = model(&username)
+        .store_with_codes(&db, &[permission.code.as_str()], &[role.code.as_str()])
+        .await?;
+    let permissions = permission_user::Entity::find()
+        .filter(permission_user::Column::UserId.eq(user.id))
+        .all(&db)
+        .await?;
+    let roles = role_user::Entity::find()
+        .filter(role_user::Column::UserId.eq(user.id))
+        .all(&db)
+        .await?;
+
+    assert_eq!(permissions.len(), 1);
+    assert_eq!(permissions[0].permission_id, permission.id);
+    assert_eq!(roles.len(), 1);
+    assert_eq!(roles[0].role_id, role.id);
+
+    // an unknown code rolls the user back too
+    let username = Uuid::new_v4().simple().to_string();
+    let result = model(&username)
+        .store_with_codes(&db, &[permission.code.as_str(), "NOT_A_PERMISSION"], &[])
+        .await;
+
+    match result {
+        Ok(_) => panic!("unknown permission code was accepted"),
+        Err(e) => assert!(e.to_string().contains("NOT_A_PERMISSION")),
+    }
+
+    assert!(!users::Model::username_exists(&db, &username).await);
+
+    Ok(())
+}
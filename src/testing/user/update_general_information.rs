@@ -44,3 +44,46 @@ pub async fn update_general_information() -> Result<(), lighter_common::prelude:
 
     Ok(())
 }
+
+#[test]
+pub async fn update_general_information_keeps_email() -> Result<(), lighter_common::prelude::Error>
+{
+    use actix_web::http::Method;
+    use actix_web::test::call_service;
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::users;
+    use crate::requests::v1::user::UserUpdateGeneralInformationRequest;
+    use crate::testing::instance::{authed_request, user};
+
+    let (service, db) = crate::service!();
+    let admin = user(&db, &["UPDATE_USER"]).await;
+    let user = user(&db, &[]).await.mark_email_verified(&db).await?;
+    let payload = UserUpdateGeneralInformationRequest {
+        name: user.name.clone(),
+        email: format!("{}@changed", Uuid::new_v4()),
+        username: user.username.clone(),
+        profile_photo_id: None,
+        permissions: vec![],
+        roles: vec![],
+    };
+
+    // an unconfirmed address can't slip in through a plain update
+    let request = authed_request(&db, &admin)
+        .await
+        .method(Method::PUT)
+        .uri(format!("/v1/user/{}", user.id).as_str())
+        .set_json(&payload)
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let unchanged = users::Model::find_by_id(&db, user.id).await.unwrap();
+
+    assert_eq!(unchanged.email, user.email);
+    assert_eq!(unchanged.email_verified_at, user.email_verified_at);
+
+    Ok(())
+}
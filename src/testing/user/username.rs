@@ -0,0 +1,96 @@
+#[test]
+pub async fn username_is_case_insensitive() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::users;
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let username = format!("Admin{}", Uuid::new_v4().simple());
+    let payload = |username: String| UserStoreRequest {
+        name: "Admin".to_string(),
+        email: format!("{}@local", Uuid::new_v4()),
+        username,
+        password: "password".to_string(),
+        password_confirmation: "password".to_string(),
+        profile_photo_id: None,
+        permissions: vec![],
+        roles: vec![],
+    };
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&payload(username.clone()))
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(users::Model::find_by_username(&db, username.to_lowercase())
+        .await
+        .is_some());
+
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&payload(username.to_lowercase()))
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    Ok(())
+}
+
+#[test]
+pub async fn username_rejects_confusables() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::entities::v1::users;
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::instance::token;
+
+    // cyrillic "а", zero-width space, fullwidth "ａ" and kelvin sign
+    for username in [
+        "\u{0430}dmin",
+        "ad\u{200B}min",
+        "\u{FF41}dmin",
+        "\u{212A}ing",
+    ] {
+        assert!(!users::Model::is_valid_username(username));
+    }
+
+    assert!(users::Model::is_valid_username("john.doe_1-2"));
+
+    let (service, db) = crate::service!();
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&UserStoreRequest {
+            name: "Admin".to_string(),
+            email: format!("{}@local", Uuid::new_v4()),
+            username: "\u{0430}dmin".to_string(),
+            password: "password".to_string(),
+            password_confirmation: "password".to_string(),
+            profile_photo_id: None,
+            permissions: vec![],
+            roles: vec![],
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    Ok(())
+}
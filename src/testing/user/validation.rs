@@ -0,0 +1,42 @@
+#[test]
+pub async fn validation_is_keyed_by_field() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, read_body, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::requests::v1::user::UserStoreRequest;
+    use crate::testing::instance::token;
+
+    let (service, db) = crate::service!();
+    let username = Uuid::new_v4().simple().to_string();
+    let request = TestRequest::default()
+        .insert_header(("Authorization", format!("Bearer {}", token(&db).await)))
+        .method(Method::POST)
+        .uri("/v1/user")
+        .set_json(&UserStoreRequest {
+            name: "John Doe".to_string(),
+            email: "not an email".to_string(),
+            username,
+            password: "password".to_string(),
+            password_confirmation: "something else".to_string(),
+            profile_photo_id: None,
+            permissions: vec![],
+            roles: vec![],
+        })
+        .to_request();
+
+    let response = call_service(&service, request).await;
+
+    assert!(response.status().is_client_error());
+
+    let body = read_body(response).await;
+    let body = String::from_utf8_lossy(&body);
+
+    // keys match the camelCase request fields
+    assert!(body.contains("\"email\""));
+    assert!(body.contains("\"passwordConfirmation\""));
+    assert!(!body.contains("\"password_confirmation\""));
+    assert!(!body.contains("\"username\""));
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+#[test]
+pub async fn version_header() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::test::{call_service, TestRequest};
+    use lighter_common::prelude::*;
+
+    use crate::middlewares::v1::version::VERSION;
+
+    let (service, _) = crate::service!();
+
+    for uri in ["/health/live", "/nowhere"] {
+        let request = TestRequest::default().uri(uri).to_request();
+        let response = call_service(&service, request).await;
+
+        assert_eq!(response.headers().get("X-Api-Version").unwrap(), VERSION);
+        assert!(!response.headers().contains_key("Deprecation"));
+    }
+
+    Ok(())
+}
+
+#[test]
+pub async fn deprecated_route() -> Result<(), lighter_common::prelude::Error> {
+    use actix_web::http::Method;
+    use actix_web::test::{call_service, init_service, TestRequest};
+    use actix_web::{web, App, HttpResponse};
+    use lighter_common::prelude::*;
+
+    use crate::middlewares::v1::version::{ApiVersionMiddleware, Deprecation};
+
+    let since = NaiveDateTime::from_timestamp_opt(1_700_000_000, 0).unwrap();
+    let sunset = NaiveDateTime::from_timestamp_opt(1_800_000_000, 0).unwrap();
+    let middleware = ApiVersionMiddleware::new().deprecate(
+        Method::GET,
+        "/legacy/{id}",
+        Deprecation {
+            since,
+            sunset: Some(sunset),
+        },
+    );
+    let service = init_service(
+        App::new().service(
+            web::scope("")
+                .wrap(middleware)
+                .route("/legacy/{id}", web::get().to(HttpResponse::Ok))
+                .route("/legacy/{id}", web::delete().to(HttpResponse::Ok))
+                .route("/current", web::get().to(HttpResponse::Ok)),
+        ),
+    )
+    .await;
+
+    let request = TestRequest::default().uri("/legacy/1").to_request();
+    let response = call_service(&service, request).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key("X-Api-Version"));
+    assert_eq!(
+        response.headers().get("Deprecation").unwrap(),
+        "@1700000000"
+    );
+    assert_eq!(
+        response.headers().get("Sunset").unwrap(),
+        "Fri, 15 Jan 2027 08:00:00 GMT"
+    );
+
+    // only the flagged method of the pattern is deprecated
+    for (method, uri) in [(Method::DELETE, "/legacy/1"), (Method::GET, "/current")] {
+        let request = TestRequest::default().method(method).uri(uri).to_request();
+        let response = call_service(&service, request).await;
+
+        assert!(response.headers().contains_key("X-Api-Version"));
+        assert!(!response.headers().contains_key("Deprecation"));
+        assert!(!response.headers().contains_key("Sunset"));
+    }
+
+    Ok(())
+}